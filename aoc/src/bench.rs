@@ -0,0 +1,260 @@
+use std::{fmt::Display, hint::black_box, path::Path, str::FromStr, time::Duration};
+
+use anyhow::{bail, Context};
+use common::{parse::Mode, Day, Part};
+
+use crate::output::format_duration;
+
+/// Step of a solver timed on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [
+        Phase::Parse,
+        Phase::Solve(Part::One),
+        Phase::Solve(Part::Two),
+    ];
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part{part}"),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            _ => match s.strip_prefix("part") {
+                Some(part) => Ok(Phase::Solve(part.parse()?)),
+                None => bail!("invalid phase `{s}`"),
+            },
+        }
+    }
+}
+
+/// Timing summary of a phase over every iteration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measure {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Time every phase of `day` on `input`, `iterations` times each.
+pub fn bench(day: &Day, input: &str, iterations: usize) -> anyhow::Result<Vec<Measure>> {
+    anyhow::ensure!(iterations > 0, "at least one iteration is required");
+
+    let mut samples = Phase::ALL.map(|_| Vec::with_capacity(iterations));
+
+    for _ in 0..iterations {
+        let (parsed, elapsed) = common::timed(|| day.parse(black_box(input), Mode::default()));
+        let parsed = parsed.with_context(|| format!("day {} failed to parse", day.number))?;
+        samples[0].push(elapsed);
+
+        for (idx, part) in Part::ALL.into_iter().enumerate() {
+            let (answer, elapsed) = common::timed(|| day.solve(&parsed, part));
+            black_box(answer.with_context(|| format!("day {} part {part} failed", day.number))?);
+            samples[idx + 1].push(elapsed);
+        }
+    }
+
+    Ok(Phase::ALL
+        .into_iter()
+        .zip(samples)
+        .map(|(phase, samples)| Measure {
+            day: day.number,
+            phase,
+            stats: Stats::from_samples(samples),
+        })
+        .collect())
+}
+
+const BASELINE_HEADER: &str = "# <day> <phase> <min_ns> <median_ns> <max_ns>\n";
+
+pub fn parse_baseline(buf: &str) -> anyhow::Result<Vec<Measure>> {
+    buf.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(idx, line)| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [day, phase, min, median, max] = fields[..] else {
+                bail!(
+                    "line {}: expected `<day> <phase> <min_ns> <median_ns> <max_ns>`",
+                    idx + 1
+                );
+            };
+
+            let nanos = |value: &str| -> anyhow::Result<Duration> {
+                let nanos = value
+                    .parse()
+                    .with_context(|| format!("line {}: invalid duration `{value}`", idx + 1))?;
+                Ok(Duration::from_nanos(nanos))
+            };
+
+            Ok(Measure {
+                day: day
+                    .parse()
+                    .with_context(|| format!("line {}: invalid day `{day}`", idx + 1))?,
+                phase: phase.parse().with_context(|| format!("line {}", idx + 1))?,
+                stats: Stats {
+                    min: nanos(min)?,
+                    median: nanos(median)?,
+                    max: nanos(max)?,
+                },
+            })
+        })
+        .collect()
+}
+
+pub fn load_baseline(path: &Path) -> anyhow::Result<Vec<Measure>> {
+    let buf = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read baseline `{}`", path.display()))?;
+    parse_baseline(&buf).with_context(|| format!("invalid baseline `{}`", path.display()))
+}
+
+/// Store `measures` in the baseline at `path`, replacing the previous
+/// measures of the same days and keeping the others.
+pub fn save_baseline(path: &Path, measures: &[Measure]) -> anyhow::Result<()> {
+    let mut baseline = if path.exists() {
+        load_baseline(path)?
+    } else {
+        Vec::new()
+    };
+    baseline.retain(|old| !measures.iter().any(|new| new.day == old.day));
+    baseline.extend_from_slice(measures);
+    baseline.sort_by_key(|m| (m.day, Phase::ALL.iter().position(|p| *p == m.phase)));
+
+    let mut buf = BASELINE_HEADER.to_owned();
+    for m in &baseline {
+        buf.push_str(&format!(
+            "{} {} {} {} {}\n",
+            m.day,
+            m.phase,
+            m.stats.min.as_nanos(),
+            m.stats.median.as_nanos(),
+            m.stats.max.as_nanos()
+        ));
+    }
+
+    std::fs::write(path, buf)
+        .with_context(|| format!("failed to write baseline `{}`", path.display()))
+}
+
+/// Relative change of the median against the baseline, `0.1` being 10% slower.
+pub fn change(measure: &Measure, baseline: &Measure) -> f64 {
+    let base = baseline.stats.median.as_secs_f64();
+    if base == 0.0 {
+        return 0.0;
+    }
+    measure.stats.median.as_secs_f64() / base - 1.0
+}
+
+/// Table of `measures`, compared against `baseline` when one is given.
+/// Also returns how many phases got slower than `threshold`.
+pub fn render(measures: &[Measure], baseline: &[Measure], threshold: f64) -> (String, usize) {
+    let mut out = format!(
+        "{:<4} {:<6} {:>10} {:>10} {:>10}\n",
+        "day", "phase", "min", "median", "max"
+    );
+    let mut slower = 0;
+
+    for m in measures {
+        out.push_str(&format!(
+            "{:<4} {:<6} {:>10} {:>10} {:>10}",
+            m.day,
+            m.phase,
+            format_duration(m.stats.min),
+            format_duration(m.stats.median),
+            format_duration(m.stats.max),
+        ));
+
+        if let Some(base) = baseline
+            .iter()
+            .find(|b| b.day == m.day && b.phase == m.phase)
+        {
+            let change = change(m, base);
+            out.push_str(&format!(
+                "  {:+.1}% vs {}",
+                change * 100.0,
+                format_duration(base.stats.median)
+            ));
+            if change > threshold {
+                out.push_str("  SLOWER");
+                slower += 1;
+            }
+        }
+        out.push('\n');
+    }
+
+    (out, slower)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn baseline_round_trip() {
+        let measure = Measure {
+            day: 6,
+            phase: Phase::Solve(Part::Two),
+            stats: Stats {
+                min: Duration::from_nanos(10),
+                median: Duration::from_nanos(12),
+                max: Duration::from_nanos(30),
+            },
+        };
+
+        let buf = format!("{BASELINE_HEADER}6 part2 10 12 30\n");
+        assert_eq!(parse_baseline(&buf).unwrap(), vec![measure]);
+    }
+
+    #[test]
+    fn flags_slowdowns() {
+        let stats = |median| Stats {
+            min: Duration::ZERO,
+            median: Duration::from_nanos(median),
+            max: Duration::from_nanos(median),
+        };
+        let measure = |median| Measure {
+            day: 1,
+            phase: Phase::Parse,
+            stats: stats(median),
+        };
+
+        let (_, slower) = render(&[measure(150)], &[measure(100)], 0.1);
+        assert_eq!(slower, 1);
+        let (_, slower) = render(&[measure(105)], &[measure(100)], 0.1);
+        assert_eq!(slower, 0);
+    }
+}
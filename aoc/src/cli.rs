@@ -0,0 +1,610 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::{bail, Context};
+use common::{input::Source, parse::Mode, visual::DEFAULT_FPS, Part};
+
+use crate::{output::Format, parallel};
+
+pub const USAGE: &str = "\
+usage: aoc <command> [options]
+
+commands:
+    run --day <N> [INPUT]    solve a single day, INPUT defaults to day_<N>/input.txt
+    run --all                solve every day with its own input.txt
+    run ... --report [--save-report <FILE>]
+                             print a table of the answers along with the parse and
+                             solve times of each part, saving it as Markdown or CSV
+                             if FILE ends in .md or .csv. Allocations and peak memory
+                             of each phase are only counted when the runner is built
+                             with `--features count-allocations`
+    verify [--day <N>] [--jobs <K>]
+                             check every day, or only day N, against its answers.txt,
+                             every input listed there being checked on its own over
+                             K threads (as many as the machine has by default)
+    verify --record          record answers of input.txt and example.input.txt
+                             for the parts missing from answers.txt
+    bench [--day <N>] [INPUT]
+                             time parsing and each part of every day, or only day N
+    gen --day <N> --size <S> [--seed <SEED>]
+                             print a random input of day N, S being day specific
+                             (number of elves, rounds, groups, moves, characters...)
+    viz --day <N> [INPUT] [--part <1|2>] [--fps <F>]
+                             replay a part of day N step by step in the terminal
+                             (days 5 to 8), driven by commands typed on stdin
+    watch --day <N> [INPUT] [--strict] [--interval <MS>]
+                             solve day N again, through cargo, every time its
+                             sources or INPUT change, showing how the answers and
+                             timings moved (polling every 500ms by default)
+    new --day <N>            create the crate of day N from the templates, wired
+                             into the workspace, the runner and the fuzz targets
+    help                     print this message
+
+bench options:
+    --iterations <K>         number of timed runs of each phase (default 100)
+    --save <FILE>            store the measures as baseline in FILE
+    --baseline <FILE>        compare the medians against the baseline stored in FILE
+    --threshold <PERCENT>    slowdown flagged against the baseline (default 10)
+
+options:
+    --part <1|2>             only solve one part of the puzzle
+    --example                use the day's bundled example.input.txt
+    --strict                 abort on the first malformed line instead of skipping it
+    --stream                 solve both parts in a single pass over INPUT, without
+                             loading it in memory (days 1, 2, 3, 4 and 6 only),
+                             elapsed_ns then covers the whole pass
+    --format <text|json>     print answers as text or as one JSON object per line
+                             ({day, part, answer, elapsed_ns}, elapsed_ns excludes parsing)
+
+INPUT is a file path, or `-` to read from stdin.";
+
+const DEFAULT_ITERATIONS: usize = 100;
+const DEFAULT_THRESHOLD: f64 = 0.1;
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    One(u8),
+    All,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Run {
+        selection: Selection,
+        parts: Vec<Part>,
+        source: Option<Source>,
+        mode: Mode,
+        format: Format,
+        stream: bool,
+        report: bool,
+        save_report: Option<PathBuf>,
+    },
+    Bench {
+        day: Option<u8>,
+        source: Option<Source>,
+        iterations: usize,
+        save: Option<PathBuf>,
+        baseline: Option<PathBuf>,
+        /// Relative slowdown, `0.1` being 10%.
+        threshold: f64,
+    },
+    Verify {
+        day: Option<u8>,
+        record: bool,
+        threads: usize,
+    },
+    Gen {
+        day: u8,
+        size: usize,
+        seed: Option<u64>,
+    },
+    Viz {
+        day: u8,
+        part: Part,
+        source: Option<Source>,
+        fps: f64,
+    },
+    Watch {
+        day: u8,
+        source: Option<Source>,
+        mode: Mode,
+        interval: Duration,
+    },
+    New {
+        day: u8,
+    },
+    Help,
+}
+
+impl Command {
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut args = args.into_iter();
+
+        match args.next().as_deref() {
+            Some("run") => Self::run_from_args(args),
+            Some("verify") => Self::verify_from_args(args),
+            Some("bench") => Self::bench_from_args(args),
+            Some("gen") => Self::gen_from_args(args),
+            Some("viz") => Self::viz_from_args(args),
+            Some("watch") => Self::watch_from_args(args),
+            Some("new") => Self::new_from_args(args),
+            Some("help" | "-h" | "--help") | None => Ok(Command::Help),
+            Some(other) => bail!("unknown command `{other}`"),
+        }
+    }
+
+    fn run_from_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Self> {
+        let mut day = None;
+        let mut all = false;
+        let mut parts = Part::ALL.to_vec();
+        let mut source = None;
+        let mut mode = Mode::default();
+        let mut format = Format::default();
+        let mut stream = false;
+        let mut report = false;
+        let mut save_report = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => {
+                    let value = args.next().context("`--day` expects a value")?;
+                    day = Some(parse_day(&value)?);
+                }
+                "--part" => {
+                    let value = args.next().context("`--part` expects a value")?;
+                    parts = vec![value.parse()?];
+                }
+                "--format" => {
+                    let value = args.next().context("`--format` expects a value")?;
+                    format = value.parse()?;
+                }
+                "--strict" => mode = Mode::Strict,
+                "--stream" => stream = true,
+                "--report" => report = true,
+                "--save-report" => {
+                    let value = args.next().context("`--save-report` expects a value")?;
+                    report = true;
+                    save_report = Some(PathBuf::from(value));
+                }
+                "--all" => all = true,
+                flag if flag.starts_with("--") && flag != "--example" => {
+                    bail!("unknown option `{flag}`")
+                }
+                arg => {
+                    if source.replace(Source::from_arg(arg)).is_some() {
+                        bail!("only one input source can be given");
+                    }
+                }
+            }
+        }
+
+        let selection = match (day, all) {
+            (Some(day), false) => Selection::One(day),
+            (None, true) => Selection::All,
+            (Some(_), true) => bail!("`--day` and `--all` are mutually exclusive"),
+            (None, false) => bail!("either `--day <N>` or `--all` is required"),
+        };
+
+        if selection == Selection::All && matches!(source, Some(Source::Path(_) | Source::Stdin)) {
+            bail!("`--all` only accepts `--example` as input source");
+        }
+
+        if report && stream {
+            bail!("`--report` needs parsing timed on its own, it cannot be used with `--stream`");
+        } else if report && format != Format::Text {
+            bail!("`--report` prints a table, it cannot be used with `--format`");
+        }
+
+        Ok(Command::Run {
+            selection,
+            parts,
+            source,
+            mode,
+            format,
+            stream,
+            report,
+            save_report,
+        })
+    }
+
+    fn bench_from_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Self> {
+        let mut day = None;
+        let mut source = None;
+        let mut iterations = DEFAULT_ITERATIONS;
+        let mut save = None;
+        let mut baseline = None;
+        let mut threshold = DEFAULT_THRESHOLD;
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .with_context(|| format!("`{name}` expects a value"))
+            };
+
+            match arg.as_str() {
+                "--day" => day = Some(parse_day(&value("--day")?)?),
+                "--iterations" => {
+                    let value = value("--iterations")?;
+                    iterations = value
+                        .parse()
+                        .with_context(|| format!("invalid iterations `{value}`"))?;
+                }
+                "--save" => save = Some(PathBuf::from(value("--save")?)),
+                "--baseline" => baseline = Some(PathBuf::from(value("--baseline")?)),
+                "--threshold" => {
+                    let value = value("--threshold")?;
+                    let percent = value
+                        .parse()
+                        .ok()
+                        .filter(|percent: &f64| percent.is_finite() && *percent >= 0.0)
+                        .with_context(|| format!("invalid threshold `{value}`"))?;
+                    threshold = percent / 100.0;
+                }
+                flag if flag.starts_with("--") && flag != "--example" => {
+                    bail!("unknown option `{flag}`")
+                }
+                arg => {
+                    if source.replace(Source::from_arg(arg)).is_some() {
+                        bail!("only one input source can be given");
+                    }
+                }
+            }
+        }
+
+        if day.is_none() && matches!(source, Some(Source::Path(_) | Source::Stdin)) {
+            bail!("benchmarking every day only accepts `--example` as input source");
+        }
+
+        Ok(Command::Bench {
+            day,
+            source,
+            iterations,
+            save,
+            baseline,
+            threshold,
+        })
+    }
+
+    fn gen_from_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Self> {
+        let mut day = None;
+        let mut size = None;
+        let mut seed = None;
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .with_context(|| format!("`{name}` expects a value"))
+            };
+
+            match arg.as_str() {
+                "--day" => day = Some(parse_day(&value("--day")?)?),
+                "--size" => {
+                    let value = value("--size")?;
+                    size = Some(
+                        value
+                            .parse()
+                            .with_context(|| format!("invalid size `{value}`"))?,
+                    );
+                }
+                "--seed" => {
+                    let value = value("--seed")?;
+                    seed = Some(
+                        value
+                            .parse()
+                            .with_context(|| format!("invalid seed `{value}`"))?,
+                    );
+                }
+                other => bail!("unexpected argument `{other}`"),
+            }
+        }
+
+        Ok(Command::Gen {
+            day: day.context("`--day <N>` is required")?,
+            size: size.context("`--size <S>` is required")?,
+            seed,
+        })
+    }
+
+    fn viz_from_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Self> {
+        let mut day = None;
+        let mut part = Part::One;
+        let mut source = None;
+        let mut fps = DEFAULT_FPS;
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .with_context(|| format!("`{name}` expects a value"))
+            };
+
+            match arg.as_str() {
+                "--day" => day = Some(parse_day(&value("--day")?)?),
+                "--part" => part = value("--part")?.parse()?,
+                "--fps" => {
+                    let value = value("--fps")?;
+                    fps = value
+                        .parse()
+                        .ok()
+                        .filter(|fps: &f64| fps.is_finite())
+                        .with_context(|| format!("invalid fps `{value}`"))?;
+                }
+                flag if flag.starts_with("--") && flag != "--example" => {
+                    bail!("unknown option `{flag}`")
+                }
+                arg => {
+                    if source.replace(Source::from_arg(arg)).is_some() {
+                        bail!("only one input source can be given");
+                    }
+                }
+            }
+        }
+
+        Ok(Command::Viz {
+            day: day.context("`--day <N>` is required")?,
+            part,
+            source,
+            fps,
+        })
+    }
+
+    fn watch_from_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Self> {
+        let mut day = None;
+        let mut source = None;
+        let mut mode = Mode::default();
+        let mut interval = DEFAULT_INTERVAL;
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .with_context(|| format!("`{name}` expects a value"))
+            };
+
+            match arg.as_str() {
+                "--day" => day = Some(parse_day(&value("--day")?)?),
+                "--strict" => mode = Mode::Strict,
+                "--interval" => {
+                    let value = value("--interval")?;
+                    interval = Duration::from_millis(
+                        value
+                            .parse()
+                            .with_context(|| format!("invalid interval `{value}`"))?,
+                    );
+                }
+                flag if flag.starts_with("--") && flag != "--example" => {
+                    bail!("unknown option `{flag}`")
+                }
+                arg => {
+                    if source.replace(Source::from_arg(arg)).is_some() {
+                        bail!("only one input source can be given");
+                    }
+                }
+            }
+        }
+
+        if source == Some(Source::Stdin) {
+            bail!("stdin cannot be watched");
+        }
+
+        Ok(Command::Watch {
+            day: day.context("`--day <N>` is required")?,
+            source,
+            mode,
+            interval,
+        })
+    }
+
+    fn new_from_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Self> {
+        let mut day = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => {
+                    let value = args.next().context("`--day` expects a value")?;
+                    day = Some(parse_day(&value)?);
+                }
+                other => bail!("unexpected argument `{other}`"),
+            }
+        }
+
+        let day = day.context("`--day <N>` is required")?;
+        if !(1..=25).contains(&day) {
+            bail!("invalid day `{day}`, expected a day between 1 and 25");
+        }
+
+        Ok(Command::New { day })
+    }
+
+    fn verify_from_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Self> {
+        let mut day = None;
+        let mut record = false;
+        let mut threads = parallel::default_threads();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => {
+                    let value = args.next().context("`--day` expects a value")?;
+                    day = Some(parse_day(&value)?);
+                }
+                "--jobs" => {
+                    let value = args.next().context("`--jobs` expects a value")?;
+                    threads = value
+                        .parse()
+                        .ok()
+                        .filter(|threads| *threads > 0)
+                        .with_context(|| format!("invalid number of jobs `{value}`"))?;
+                }
+                "--record" => record = true,
+                other => bail!("unexpected argument `{other}`"),
+            }
+        }
+
+        Ok(Command::Verify {
+            day,
+            record,
+            threads,
+        })
+    }
+}
+
+fn parse_day(value: &str) -> anyhow::Result<u8> {
+    value
+        .parse()
+        .with_context(|| format!("invalid day `{value}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn parse(args: &[&str]) -> anyhow::Result<Command> {
+        Command::from_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn viz(fps: &str) -> anyhow::Result<Command> {
+        parse(&["viz", "--day", "8", "--fps", fps])
+    }
+
+    #[test]
+    fn run() {
+        assert_eq!(
+            parse(&["run", "--day", "3", "--part", "2", "--strict", "--format", "json", "-"])
+                .unwrap(),
+            Command::Run {
+                selection: Selection::One(3),
+                parts: vec![Part::Two],
+                source: Some(Source::Stdin),
+                mode: Mode::Strict,
+                format: Format::Json,
+                stream: false,
+                report: false,
+                save_report: None,
+            }
+        );
+        assert!(matches!(
+            parse(&["run", "--all", "--example", "--save-report", "times.md"]).unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                source: Some(Source::Example),
+                report: true,
+                save_report: Some(path),
+                ..
+            } if path == Path::new("times.md")
+        ));
+
+        for args in [
+            &["run"][..],
+            &["run", "--day", "1", "--all"],
+            &["run", "--all", "input.txt"],
+            &["run", "--day", "1", "--format", "yaml"],
+            &["run", "--day", "1", "--report", "--stream"],
+            &["run", "--day", "1", "--report", "--format", "json"],
+            &["run", "--day", "1", "a.txt", "b.txt"],
+            &["run", "--day"],
+        ] {
+            assert!(parse(args).is_err(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn bench() {
+        assert_eq!(
+            parse(&[
+                "bench",
+                "--day",
+                "2",
+                "--iterations",
+                "5",
+                "--threshold",
+                "2.5"
+            ])
+            .unwrap(),
+            Command::Bench {
+                day: Some(2),
+                source: None,
+                iterations: 5,
+                save: None,
+                baseline: None,
+                threshold: 0.025,
+            }
+        );
+        assert!(matches!(
+            parse(&["bench"]).unwrap(),
+            Command::Bench { threshold, .. } if threshold == DEFAULT_THRESHOLD
+        ));
+        assert!(matches!(
+            parse(&["bench", "--threshold", "0"]).unwrap(),
+            Command::Bench { threshold, .. } if threshold == 0.0
+        ));
+
+        for threshold in ["-5", "NaN", "inf", "slow"] {
+            assert!(
+                parse(&["bench", "--threshold", threshold]).is_err(),
+                "{threshold}"
+            );
+        }
+        assert!(parse(&["bench", "input.txt"]).is_err());
+    }
+
+    #[test]
+    fn verify() {
+        assert_eq!(
+            parse(&["verify", "--day", "4", "--jobs", "3", "--record"]).unwrap(),
+            Command::Verify {
+                day: Some(4),
+                record: true,
+                threads: 3,
+            }
+        );
+
+        for jobs in ["0", "-1", "many"] {
+            assert!(parse(&["verify", "--jobs", jobs]).is_err(), "{jobs}");
+        }
+        assert!(parse(&["verify", "--jobs"]).is_err());
+    }
+
+    #[test]
+    fn other_commands() {
+        assert_eq!(
+            parse(&["gen", "--day", "5", "--size", "10", "--seed", "7"]).unwrap(),
+            Command::Gen {
+                day: 5,
+                size: 10,
+                seed: Some(7),
+            }
+        );
+        assert_eq!(
+            parse(&["watch", "--day", "6", "--strict", "--interval", "50"]).unwrap(),
+            Command::Watch {
+                day: 6,
+                source: None,
+                mode: Mode::Strict,
+                interval: Duration::from_millis(50),
+            }
+        );
+        assert_eq!(
+            parse(&["new", "--day", "9"]).unwrap(),
+            Command::New { day: 9 }
+        );
+        assert_eq!(parse(&[]).unwrap(), Command::Help);
+
+        for args in [
+            &["gen", "--day", "5"][..],
+            &["watch", "--day", "6", "-"],
+            &["new", "--day", "26"],
+            &["fly"],
+        ] {
+            assert!(parse(args).is_err(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn fps() {
+        assert!(matches!(viz("2.5"), Ok(Command::Viz { fps, .. }) if fps == 2.5));
+
+        for fps in ["NaN", "inf", "-inf", "fast"] {
+            assert!(viz(fps).is_err(), "{fps}");
+        }
+    }
+}
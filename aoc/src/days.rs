@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use common::{
+    input::{Source, DEFAULT_INPUT_PATH},
+    Day,
+};
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day_1::Day1>()
+        .with_generator::<day_1::Day1>()
+        .with_streaming::<day_1::Day1>(),
+    Day::of::<day_2::Day2>()
+        .with_generator::<day_2::Day2>()
+        .with_streaming::<day_2::Day2>(),
+    Day::of::<day_3::Day3>()
+        .with_generator::<day_3::Day3>()
+        .with_streaming::<day_3::Day3>(),
+    Day::of::<day_4::Day4>()
+        .with_generator::<day_4::Day4>()
+        .with_streaming::<day_4::Day4>(),
+    Day::of::<day_5::Day5>()
+        .with_generator::<day_5::Day5>()
+        .with_visualizer::<day_5::Day5>(),
+    Day::of::<day_6::Day6>()
+        .with_generator::<day_6::Day6>()
+        .with_streaming::<day_6::Day6>()
+        .with_visualizer::<day_6::Day6>(),
+    Day::of::<day_7::Day7>()
+        .with_generator::<day_7::Day7>()
+        .with_visualizer::<day_7::Day7>(),
+    Day::of::<day_8::Day8>()
+        .with_generator::<day_8::Day8>()
+        .with_visualizer::<day_8::Day8>(),
+];
+
+pub fn find(number: u8) -> anyhow::Result<&'static Day> {
+    DAYS.iter()
+        .find(|day| day.number == number)
+        .with_context(|| format!("day {number} is not solved yet"))
+}
+
+/// Root of the workspace, holding a crate per day.
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner is a member of the workspace")
+        .to_path_buf()
+}
+
+/// Directory of the day's crate, where its puzzle inputs live.
+pub fn dir(day: &Day) -> PathBuf {
+    root().join(format!("day_{}", day.number))
+}
+
+/// The day's own puzzle input.
+pub fn input(day: &Day) -> Source {
+    Source::Path(dir(day).join(DEFAULT_INPUT_PATH))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{generator::Rng, parse::Mode};
+
+    use super::*;
+
+    /// `input` with a few of its characters replaced, removed or doubled.
+    fn mutate(rng: &mut Rng, input: &str) -> String {
+        const NOISE: &[char] = &[' ', '\n', '-', ',', '[', ']', '$', '0', '9', 'A', 'z', 'é'];
+        let mut chars: Vec<char> = input.chars().collect();
+
+        for _ in 0..rng.range(1..=4) {
+            if chars.is_empty() {
+                break;
+            }
+            let idx = rng.range(0..=chars.len() - 1);
+            match rng.range(0..=2) {
+                0 => chars[idx] = *rng.choose(NOISE),
+                1 => {
+                    chars.remove(idx);
+                }
+                _ => chars.insert(idx, chars[idx]),
+            }
+        }
+
+        chars.into_iter().collect()
+    }
+
+    /// Quick stand-in for the `fuzz/` targets, runnable on stable.
+    #[test]
+    fn parsers_never_panic() {
+        let mut rng = Rng::new(0xf022);
+
+        for day in DAYS {
+            for _ in 0..500 {
+                let input = mutate(&mut rng, day.example);
+                let prefix: String = input.chars().take(rng.range(0..=input.len())).collect();
+
+                for input in [&input, &prefix] {
+                    for mode in [Mode::Lenient, Mode::Strict] {
+                        let _ = day.parse(input, mode);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+mod bench;
+mod cli;
+mod days;
+mod output;
+mod parallel;
+mod report;
+mod scaffold;
+mod verify;
+mod watch;
+
+use anyhow::Context;
+use cli::{Command, Selection};
+use common::{generator::Rng, input::Source, parse::Mode, Day, Part};
+use output::{format_duration, Format, Record};
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: common::alloc::Counting<std::alloc::System> =
+    common::alloc::Counting::new(std::alloc::System);
+
+fn run(
+    day: &Day,
+    parts: &[Part],
+    source: &Source,
+    mode: Mode,
+    format: Format,
+) -> anyhow::Result<()> {
+    for row in report::solve(day, parts, source, mode)? {
+        let record = Record {
+            day: row.day,
+            part: row.part,
+            answer: row.answer,
+            elapsed: row.solve,
+        };
+        println!("{}", record.render(format));
+    }
+
+    Ok(())
+}
+
+/// Same as [`run`], reading the input in a single pass instead of loading it.
+fn stream(
+    day: &Day,
+    parts: &[Part],
+    source: &Source,
+    mode: Mode,
+    format: Format,
+) -> anyhow::Result<()> {
+    let mut reader = source.open(day.example)?;
+
+    let (answers, elapsed) = common::timed(|| day.stream(&mut reader, mode));
+    let (one, two) = answers.with_context(|| format!("day {} failed", day.number))?;
+
+    for &part in parts {
+        let answer = match part {
+            Part::One => one.clone(),
+            Part::Two => two.clone(),
+        };
+
+        let record = Record {
+            day: day.number,
+            part,
+            answer,
+            elapsed,
+        };
+        println!("{}", record.render(format));
+    }
+
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    match Command::from_args(std::env::args().skip(1))? {
+        Command::Run {
+            selection,
+            parts,
+            source,
+            mode,
+            format,
+            stream,
+            report,
+            save_report,
+        } => {
+            let selected = match selection {
+                Selection::One(number) => std::slice::from_ref(days::find(number)?),
+                Selection::All => days::DAYS,
+            };
+
+            if report {
+                let mut rows = Vec::new();
+                for day in selected {
+                    let source = source.clone().unwrap_or_else(|| days::input(day));
+                    rows.extend(report::solve(day, &parts, &source, mode)?);
+                }
+
+                print!("{}", report::table(&rows));
+                if let Some(path) = save_report {
+                    report::save(&rows, &path)?;
+                    println!("report saved to {}", path.display());
+                }
+                return Ok(());
+            }
+
+            let run = if stream { self::stream } else { run };
+            selected.iter().try_for_each(|day| {
+                let source = source.clone().unwrap_or_else(|| days::input(day));
+                run(day, &parts, &source, mode, format)
+            })
+        }
+        Command::Bench {
+            day,
+            source,
+            iterations,
+            save,
+            baseline,
+            threshold,
+        } => {
+            let selected = match day {
+                Some(number) => std::slice::from_ref(days::find(number)?),
+                None => days::DAYS,
+            };
+
+            let mut measures = Vec::new();
+            for day in selected {
+                let source = source.clone().unwrap_or_else(|| days::input(day));
+                let input = source.read(day.example)?;
+                measures.extend(bench::bench(day, &input, iterations)?);
+            }
+
+            let baseline = match &baseline {
+                Some(path) => bench::load_baseline(path)?,
+                None => Vec::new(),
+            };
+
+            let (table, slower) = bench::render(&measures, &baseline, threshold);
+            println!("{iterations} iterations per phase");
+            print!("{table}");
+
+            if let Some(path) = save {
+                bench::save_baseline(&path, &measures)?;
+                println!("baseline saved to {}", path.display());
+            }
+
+            if slower > 0 {
+                anyhow::bail!("{slower} phases are slower than the baseline");
+            }
+            Ok(())
+        }
+        Command::Verify {
+            day,
+            record,
+            threads,
+        } => {
+            let selected = match day {
+                Some(number) => std::slice::from_ref(days::find(number)?),
+                None => days::DAYS,
+            };
+
+            if record {
+                for day in selected {
+                    for e in verify::record(day)? {
+                        println!(
+                            "recorded    day {} part {} {}: {}",
+                            day.number, e.part, e.input, e.answer
+                        );
+                    }
+                }
+                return Ok(());
+            }
+
+            let mut jobs = Vec::new();
+            for day in selected {
+                jobs.extend(verify::jobs(day)?);
+            }
+
+            let (results, elapsed) =
+                common::timed(|| parallel::map(&jobs, threads, verify::Job::run));
+            let checks = results
+                .iter()
+                .flat_map(|(checks, _)| checks)
+                .collect::<Vec<_>>();
+            let solving = results.iter().map(|(_, elapsed)| *elapsed).sum();
+
+            for check in &checks {
+                println!("{check}");
+            }
+
+            let failed = checks.iter().filter(|check| !check.passed()).count();
+            let threads = threads.min(jobs.len().max(1));
+            println!(
+                "{} passed, {failed} failed in {} ({} of solving {} inputs over {threads} thread{})",
+                checks.len() - failed,
+                format_duration(elapsed),
+                format_duration(solving),
+                jobs.len(),
+                if threads == 1 { "" } else { "s" }
+            );
+
+            if failed > 0 {
+                anyhow::bail!("verification failed for {failed} recorded answers");
+            }
+            Ok(())
+        }
+        Command::Gen { day, size, seed } => {
+            let mut rng = seed.map_or_else(Rng::from_time, Rng::new);
+            print!("{}", days::find(day)?.generate(&mut rng, size)?);
+            Ok(())
+        }
+        Command::Viz {
+            day,
+            part,
+            source,
+            fps,
+        } => {
+            let day = days::find(day)?;
+            let source = source.unwrap_or_else(|| days::input(day));
+            let parsed = day.parse(&source.read(day.example)?, Mode::Lenient)?;
+
+            let animation = day.visualize(&parsed, part)?;
+            common::visual::play(animation.as_ref(), fps)
+        }
+        Command::Watch {
+            day,
+            source,
+            mode,
+            interval,
+        } => {
+            let day = days::find(day)?;
+            let source = source.unwrap_or_else(|| days::input(day));
+            watch::watch(day, &source, mode, interval)
+        }
+        Command::New { day } => {
+            let dir = scaffold::new_day(day)?;
+            println!("created {}", dir.display());
+            println!(
+                "fill in example.input.txt and its answers in tests/example.rs, \
+                 then put the puzzle input in input.txt"
+            );
+            Ok(())
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::{str::FromStr, time::Duration};
+
+use anyhow::Context;
+use common::{
+    json::{self, Value},
+    Part,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(anyhow::anyhow!(
+                "invalid format `{s}`, expected text or json"
+            )),
+        }
+    }
+}
+
+/// Human readable duration, with a unit suited to its magnitude.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    match nanos {
+        0..=999 => format!("{nanos}ns"),
+        1_000..=999_999 => format!("{:.2}µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", d.as_secs_f64()),
+    }
+}
+
+/// Human readable amount of memory, in binary units.
+pub fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes}B"),
+        1024..=1_048_575 => format!("{:.2}KiB", bytes as f64 / 1024.0),
+        1_048_576..=1_073_741_823 => format!("{:.2}MiB", bytes as f64 / 1_048_576.0),
+        _ => format!("{:.2}GiB", bytes as f64 / 1_073_741_824.0),
+    }
+}
+
+/// Answer of a single part, along with the time it took to solve it.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+impl Record {
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text => format!("Day {} part {}: {}", self.day, self.part, self.answer),
+            Format::Json => json::Object::new()
+                .number("day", self.day)
+                .number("part", self.part.number())
+                .string("answer", &self.answer)
+                .number("elapsed_ns", self.elapsed.as_nanos())
+                .finish(),
+        }
+    }
+
+    /// Read back a record rendered as JSON.
+    pub fn from_json(line: &str) -> anyhow::Result<Self> {
+        let fields = json::parse_object(line)?;
+        let field = |key: &str| {
+            fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value)
+                .with_context(|| format!("missing `{key}` in `{line}`"))
+        };
+        let number = |key: &str| match field(key)? {
+            Value::Number(n) => Ok(*n),
+            _ => anyhow::bail!("`{key}` should be a number in `{line}`"),
+        };
+
+        Ok(Record {
+            day: number("day")?.try_into().context("invalid day")?,
+            part: number("part")?.to_string().parse()?,
+            answer: match field("answer")? {
+                Value::String(answer) => answer.clone(),
+                _ => anyhow::bail!("`answer` should be a string in `{line}`"),
+            },
+            elapsed: Duration::from_nanos(number("elapsed_ns")?.try_into()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_round_trip() {
+        let record = Record {
+            day: 5,
+            part: Part::Two,
+            answer: "MCD".to_owned(),
+            elapsed: Duration::from_micros(12),
+        };
+
+        let read = Record::from_json(&record.render(Format::Json)).unwrap();
+        assert_eq!(
+            (read.day, read.part, read.answer, read.elapsed),
+            (5, Part::Two, "MCD".to_owned(), record.elapsed)
+        );
+        assert!(Record::from_json(r#"{"day":5}"#).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Number of threads to use when none is asked for.
+pub fn default_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// `f` applied to every item, spread over up to `threads` threads taking
+/// the next item as soon as they are done with one. Results come back in the
+/// order of `items`, whatever the order they were computed in.
+pub fn map<T: Sync, R: Send>(items: &[T], threads: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let threads = threads.clamp(1, items.len().max(1));
+
+    let mut results = std::thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(idx) else {
+                            return done;
+                        };
+                        done.push((idx, f(item)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_unstable_by_key(|(idx, _)| *idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn keeps_the_order_of_items() {
+        let items = (0..50u64).collect::<Vec<_>>();
+
+        // Early items take longer, so that they finish last.
+        let squares = map(&items, 4, |n| {
+            std::thread::sleep(Duration::from_micros(50 - n));
+            n * n
+        });
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn any_number_of_threads() {
+        assert_eq!(map(&[1, 2, 3], 0, |n| n + 1), vec![2, 3, 4]);
+        assert_eq!(map(&[1, 2, 3], 64, |n| n + 1), vec![2, 3, 4]);
+        assert!(map(&[] as &[u8], 4, |n| *n).is_empty());
+    }
+}
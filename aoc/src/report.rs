@@ -0,0 +1,274 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::{bail, Context};
+use common::{input::Source, parse::Mode, Day, Part};
+
+use common::alloc::{self, Usage};
+
+use crate::output::{format_bytes, format_duration};
+
+/// Answer of a part along with what it cost to get it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    /// Shared by both parts, the input being parsed once.
+    pub parse: Duration,
+    pub solve: Duration,
+    /// Memory used by each phase, when allocations are counted.
+    pub parse_memory: Option<Usage>,
+    pub solve_memory: Option<Usage>,
+}
+
+/// Parse the input of `day` read from `source`, then solve each of `parts`.
+pub fn solve(day: &Day, parts: &[Part], source: &Source, mode: Mode) -> anyhow::Result<Vec<Row>> {
+    let buf = source.read(day.example)?;
+
+    let counting = alloc::is_counting();
+
+    let ((parsed, parse), parse_memory) =
+        alloc::measure(|| common::timed(|| day.parse(&buf, mode)));
+    let parsed = parsed.with_context(|| format!("day {} failed to parse its input", day.number))?;
+
+    parts
+        .iter()
+        .map(|&part| {
+            let ((answer, solve), solve_memory) =
+                alloc::measure(|| common::timed(|| day.solve(&parsed, part)));
+
+            Ok(Row {
+                day: day.number,
+                part,
+                answer: answer.with_context(|| format!("day {} part {part} failed", day.number))?,
+                parse,
+                solve,
+                parse_memory: counting.then_some(parse_memory),
+                solve_memory: counting.then_some(solve_memory),
+            })
+        })
+        .collect()
+}
+
+impl Row {
+    /// Memory used to parse the input then solve the part, the parsed input
+    /// staying alive while solving.
+    pub fn memory(&self) -> Option<Usage> {
+        Some(self.parse_memory?.then(self.solve_memory?))
+    }
+}
+
+/// File format of a saved report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Csv,
+}
+
+impl Format {
+    /// Format matching the extension of `path`, `.md` or `.csv`.
+    pub fn of(path: &Path) -> anyhow::Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("md") => Ok(Format::Markdown),
+            Some("csv") => Ok(Format::Csv),
+            _ => bail!(
+                "cannot tell the format of `{}`, expected a .md or .csv file",
+                path.display()
+            ),
+        }
+    }
+}
+
+const HEADER: [&str; 9] = [
+    "day",
+    "part",
+    "answer",
+    "parse",
+    "solve",
+    "parse allocs",
+    "solve allocs",
+    "peak allocs",
+    "peak memory",
+];
+
+fn cells(row: &Row) -> [String; 9] {
+    let memory = |f: fn(&Row) -> Option<String>| f(row).unwrap_or_else(|| "-".to_owned());
+
+    [
+        row.day.to_string(),
+        row.part.to_string(),
+        row.answer.clone(),
+        format_duration(row.parse),
+        format_duration(row.solve),
+        memory(|row| Some(row.parse_memory?.allocations.to_string())),
+        memory(|row| Some(row.solve_memory?.allocations.to_string())),
+        memory(|row| Some(row.memory()?.peak_live.to_string())),
+        memory(|row| Some(format_bytes(row.memory()?.peak_bytes))),
+    ]
+}
+
+/// Table of `rows` aligned for the terminal.
+pub fn table(rows: &[Row]) -> String {
+    let rows = rows.iter().map(cells).collect::<Vec<_>>();
+    let widths = rows.iter().fold(HEADER.map(str::len), |mut widths, cells| {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+        widths
+    });
+
+    let line = |cells: &[String]| {
+        let mut out = String::new();
+        for (idx, (cell, width)) in cells.iter().zip(widths).enumerate() {
+            // Text is left aligned, numbers and durations right aligned.
+            let cell = if idx == 2 {
+                format!("{cell:<width$}")
+            } else {
+                format!("{cell:>width$}")
+            };
+            out.push_str(&cell);
+            out.push_str("  ");
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    };
+
+    let mut out = line(&HEADER.map(str::to_owned));
+    for cells in &rows {
+        out.push_str(&line(cells));
+    }
+    out
+}
+
+/// Report of `rows` as a file in `format`.
+pub fn render(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Markdown => {
+            let mut out = format!("| {} |\n", HEADER.join(" | "));
+            out.push_str("|---:|---:|---|---:|---:|---:|---:|---:|---:|\n");
+            for cells in rows.iter().map(cells) {
+                let cells = cells.map(|cell| cell.replace('|', "\\|"));
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+            out
+        }
+        Format::Csv => {
+            // Plain nanoseconds and bytes, so that they sort as numbers, memory
+            // being left empty when allocations are not counted.
+            let mut out = "day,part,answer,parse_ns,solve_ns,parse_allocs,solve_allocs,peak_allocs,peak_bytes\n".to_owned();
+            for row in rows {
+                let memory = match (row.parse_memory, row.solve_memory, row.memory()) {
+                    (Some(parse), Some(solve), Some(total)) => format!(
+                        "{},{},{},{}",
+                        parse.allocations, solve.allocations, total.peak_live, total.peak_bytes
+                    ),
+                    _ => ",,,".to_owned(),
+                };
+                out.push_str(&format!(
+                    "{},{},{},{},{},{memory}\n",
+                    row.day,
+                    row.part,
+                    csv_field(&row.answer),
+                    row.parse.as_nanos(),
+                    row.solve.as_nanos(),
+                ));
+            }
+            out
+        }
+    }
+}
+
+/// `field` quoted when it would break the line into extra fields.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Write the report of `rows` to `path`, in the format its extension tells.
+pub fn save(rows: &[Row], path: &Path) -> anyhow::Result<()> {
+    let format = Format::of(path)?;
+    std::fs::write(path, render(rows, format))
+        .with_context(|| format!("failed to write report `{}`", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                day: 5,
+                part: Part::One,
+                answer: "CMZ".to_owned(),
+                parse: Duration::from_micros(12),
+                solve: Duration::from_nanos(800),
+                parse_memory: Some(Usage {
+                    allocations: 10,
+                    peak_live: 9,
+                    peak_bytes: 2048,
+                    live: 8,
+                    bytes: 1024,
+                }),
+                solve_memory: Some(Usage {
+                    allocations: 4,
+                    peak_live: 2,
+                    peak_bytes: 1536,
+                    live: 0,
+                    bytes: 0,
+                }),
+            },
+            Row {
+                day: 12,
+                part: Part::Two,
+                answer: "a,b|c".to_owned(),
+                parse: Duration::from_millis(3),
+                solve: Duration::from_secs(2),
+                parse_memory: None,
+                solve_memory: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn terminal_table() {
+        assert_eq!(
+            table(&rows()),
+            "\
+day  part  answer    parse  solve  parse allocs  solve allocs  peak allocs  peak memory
+  5     1  CMZ     12.00µs  800ns            10             4           10      2.50KiB
+ 12     2  a,b|c    3.00ms  2.00s             -             -            -            -
+"
+        );
+    }
+
+    #[test]
+    fn markdown_and_csv() {
+        let markdown = render(&rows(), Format::Markdown);
+        assert_eq!(
+            markdown.lines().nth(3),
+            Some("| 12 | 2 | a,b\\|c | 3.00ms | 2.00s | - | - | - | - |")
+        );
+
+        assert_eq!(
+            render(&rows(), Format::Csv),
+            "day,part,answer,parse_ns,solve_ns,parse_allocs,solve_allocs,peak_allocs,peak_bytes\n\
+             5,1,CMZ,12000,800,10,4,10,2560\n\
+             12,2,\"a,b|c\",3000000,2000000000,,,,\n"
+        );
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(
+            Format::of(Path::new("wiki/report.md")).unwrap(),
+            Format::Markdown
+        );
+        assert_eq!(Format::of(Path::new("report.csv")).unwrap(), Format::Csv);
+        assert!(Format::of(Path::new("report.txt")).is_err());
+    }
+}
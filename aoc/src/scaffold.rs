@@ -0,0 +1,211 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+
+use crate::days;
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+const EXAMPLE_RS: &str = include_str!("../templates/example.rs.tmpl");
+const FUZZ_TARGET_RS: &str = include_str!("../templates/fuzz_target.rs.tmpl");
+
+/// `template` with every `{{day}}` replaced by `day`.
+pub fn render(template: &str, day: u8) -> String {
+    template.replace("{{day}}", &day.to_string())
+}
+
+/// Insert `line` right before the first line equal to `end` following the
+/// first line equal to `start`.
+fn insert_before(buf: &str, start: &str, end: &str, line: &str) -> anyhow::Result<String> {
+    let mut out = String::with_capacity(buf.len() + line.len() + 1);
+    let mut lines = buf.lines();
+
+    for current in lines.by_ref() {
+        out.push_str(current);
+        out.push('\n');
+        if current == start {
+            break;
+        }
+    }
+    for current in lines.by_ref() {
+        if current == end {
+            out.push_str(line);
+            out.push('\n');
+            out.push_str(current);
+            out.push('\n');
+            break;
+        }
+        out.push_str(current);
+        out.push('\n');
+    }
+    for current in lines {
+        out.push_str(current);
+        out.push('\n');
+    }
+
+    anyhow::ensure!(
+        out.contains(&format!("{line}\n{end}\n")),
+        "no `{start}` ... `{end}` block found"
+    );
+    Ok(out)
+}
+
+/// The workspace manifest, with the day's crate added to its members.
+pub fn add_member(manifest: &str, day: u8) -> anyhow::Result<String> {
+    let member = format!("    \"day_{day}\",");
+    if manifest.lines().any(|line| line == member) {
+        bail!("day_{day} is already a member of the workspace");
+    }
+    insert_before(manifest, "members = [", "]", &member)
+}
+
+/// A manifest with the day's crate added after the last day it depends on.
+pub fn add_dependency(manifest: &str, day: u8) -> anyhow::Result<String> {
+    let dependency = format!("day_{day} = {{ path = \"../day_{day}\" }}");
+    if manifest.lines().any(|line| line == dependency) {
+        bail!("day_{day} is already a dependency");
+    }
+
+    let mut lines = manifest.lines().collect::<Vec<_>>();
+    let last = lines
+        .iter()
+        .rposition(|line| line.starts_with("day_"))
+        .context("no day dependency to add the new one after")?;
+    lines.insert(last + 1, &dependency);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The runner's `days.rs`, with the day appended to `DAYS`.
+pub fn register(days_rs: &str, day: u8) -> anyhow::Result<String> {
+    let entry = format!("    Day::of::<day_{day}::Day{day}>(),");
+    if days_rs.contains(&format!("day_{day}::Day{day}")) {
+        bail!("day {day} is already registered");
+    }
+    insert_before(days_rs, "pub const DAYS: &[Day] = &[", "];", &entry)
+}
+
+/// The fuzzing manifest, with the day as dependency and as fuzz target.
+pub fn add_fuzz_target(manifest: &str, day: u8) -> anyhow::Result<String> {
+    let mut manifest = add_dependency(manifest, day)?;
+    manifest.push_str(&render(
+        "\n[[bin]]\nname = \"day_{{day}}\"\npath = \"fuzz_targets/day_{{day}}.rs\"\ntest = false\ndoc = false\nbench = false\n",
+        day,
+    ));
+    Ok(manifest)
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("failed to read `{}`", path.display()))
+}
+
+/// Create the crate of `day` from the templates and wire it into the
+/// workspace, the runner and the fuzz targets. Returns the created crate.
+pub fn new_day(day: u8) -> anyhow::Result<PathBuf> {
+    let root = days::root();
+    let dir = root.join(format!("day_{day}"));
+    if dir.exists() {
+        bail!("`{}` already exists", dir.display());
+    }
+
+    // Every edit is computed first, so that nothing is written when one fails.
+    let edits = [
+        (root.join("Cargo.toml"), add_member as fn(&str, u8) -> _),
+        (root.join("aoc").join("Cargo.toml"), add_dependency),
+        (root.join("aoc").join("src").join("days.rs"), register),
+        (root.join("fuzz").join("Cargo.toml"), add_fuzz_target),
+    ]
+    .into_iter()
+    .map(|(path, edit)| {
+        let edited = edit(&read(&path)?, day)
+            .with_context(|| format!("failed to edit `{}`", path.display()))?;
+        Ok((path, edited))
+    })
+    .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let files = [
+        (dir.join("Cargo.toml"), render(CARGO_TOML, day)),
+        (dir.join("src").join("lib.rs"), render(LIB_RS, day)),
+        (dir.join("src").join("main.rs"), render(MAIN_RS, day)),
+        (
+            dir.join("tests").join("example.rs"),
+            render(EXAMPLE_RS, day),
+        ),
+        (dir.join("example.input.txt"), String::new()),
+        (
+            root.join("fuzz")
+                .join("fuzz_targets")
+                .join(format!("day_{day}.rs")),
+            render(FUZZ_TARGET_RS, day),
+        ),
+    ];
+
+    for (path, content) in files.into_iter().chain(edits) {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create `{}`", parent.display()))?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("failed to write `{}`", path.display()))?;
+    }
+
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_member() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day_1\",\n]\n";
+
+        assert_eq!(
+            add_member(manifest, 2).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day_1\",\n    \"day_2\",\n]\n"
+        );
+        assert!(add_member(manifest, 1).is_err());
+        assert!(add_member("[workspace]\n", 2).is_err());
+    }
+
+    #[test]
+    fn runner_dependency() {
+        let manifest = "[dependencies]\nday_1 = { path = \"../day_1\" }\nlibfuzzer-sys = \"0.4\"\n";
+
+        assert_eq!(
+            add_dependency(manifest, 2).unwrap(),
+            "[dependencies]\nday_1 = { path = \"../day_1\" }\nday_2 = { path = \"../day_2\" }\nlibfuzzer-sys = \"0.4\"\n"
+        );
+        assert!(add_dependency(manifest, 1).is_err());
+    }
+
+    #[test]
+    fn registration() {
+        let days_rs =
+            "pub const DAYS: &[Day] = &[\n    Day::of::<day_1::Day1>(),\n];\n\nfn f() {}\n";
+
+        assert_eq!(
+            register(days_rs, 2).unwrap(),
+            "pub const DAYS: &[Day] = &[\n    Day::of::<day_1::Day1>(),\n    Day::of::<day_2::Day2>(),\n];\n\nfn f() {}\n"
+        );
+        assert!(register(days_rs, 1).is_err());
+    }
+
+    #[test]
+    fn current_tree_accepts_a_new_day() {
+        let root = days::root();
+
+        add_member(&read(&root.join("Cargo.toml")).unwrap(), 25).unwrap();
+        register(&read(&root.join("aoc/src/days.rs")).unwrap(), 25).unwrap();
+        add_fuzz_target(&read(&root.join("fuzz/Cargo.toml")).unwrap(), 25).unwrap();
+    }
+
+    #[test]
+    fn templates() {
+        let lib = render(LIB_RS, 9);
+
+        assert!(lib.contains("pub struct Day9;"));
+        assert!(lib.contains("const DAY: u8 = 9;"));
+        assert!(!lib.contains("{{"));
+    }
+}
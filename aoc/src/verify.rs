@@ -0,0 +1,294 @@
+use std::{fmt::Display, path::Path, time::Duration};
+
+use anyhow::{bail, Context};
+use common::{input::Source, parse::Mode, Day, Part};
+
+use crate::days;
+
+/// Known-correct answers of a day, stored next to its inputs.
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+const ANSWERS_HEADER: &str = "# <input> <part> <answer>\n";
+
+/// Inputs whose answers are recorded by `aoc verify --record`.
+const RECORDED_INPUTS: &[&str] = &["input.txt", "example.input.txt"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    /// Input path, relative to the day's directory.
+    pub input: String,
+    pub part: Part,
+    pub answer: String,
+}
+
+pub fn parse_answers(buf: &str) -> anyhow::Result<Vec<Expected>> {
+    buf.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(idx, line)| {
+            let mut splitted = line.splitn(3, ' ');
+            let (Some(input), Some(part), Some(answer)) =
+                (splitted.next(), splitted.next(), splitted.next())
+            else {
+                bail!("line {}: expected `<input> <part> <answer>`", idx + 1);
+            };
+
+            Ok(Expected {
+                input: input.to_owned(),
+                part: part.parse().with_context(|| format!("line {}", idx + 1))?,
+                answer: answer.to_owned(),
+            })
+        })
+        .collect()
+}
+
+fn load_answers(path: &Path) -> anyhow::Result<Vec<Expected>> {
+    match std::fs::read_to_string(path) {
+        Ok(buf) => parse_answers(&buf)
+            .with_context(|| format!("invalid answers file `{}`", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => {
+            Err(e).with_context(|| format!("failed to read answers file `{}`", path.display()))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Status {
+    Pass,
+    /// The solver produced a different answer than the recorded one.
+    Regression {
+        got: String,
+    },
+    /// The solver did not produce any answer.
+    Fail(anyhow::Error),
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub expected: Expected,
+    pub status: Status,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        matches!(self.status, Status::Pass)
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Expected {
+            input,
+            part,
+            answer,
+        } = &self.expected;
+        let day = self.day;
+
+        match &self.status {
+            Status::Pass => write!(f, "pass        day {day} part {part} {input}: {answer}"),
+            Status::Regression { got } => write!(
+                f,
+                "regression  day {day} part {part} {input}: expected {answer}, got {got}"
+            ),
+            Status::Fail(e) => write!(f, "fail        day {day} part {part} {input}: {e:#}"),
+        }
+    }
+}
+
+/// Answers of `parts` for `input`, or the reason no answer could be computed.
+fn solve(day: &Day, input: &str, parts: &[Part]) -> Vec<anyhow::Result<String>> {
+    let source = Source::Path(days::dir(day).join(input));
+    let parsed = source
+        .read(day.example)
+        .and_then(|buf| day.parse(&buf, Mode::default()));
+
+    parts
+        .iter()
+        .map(|&part| match &parsed {
+            Ok(parsed) => day.solve(parsed, part),
+            Err(e) => Err(anyhow::anyhow!("{e:#}")),
+        })
+        .collect()
+}
+
+/// Answers recorded for a day on a single input, checked independently of
+/// every other job.
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub day: &'static Day,
+    /// Input path, relative to the day's directory.
+    pub input: String,
+    pub expected: Vec<Expected>,
+}
+
+/// One job per input `day` has answers recorded for, in the order they first
+/// appear in its answers file.
+pub fn jobs(day: &'static Day) -> anyhow::Result<Vec<Job>> {
+    let mut jobs: Vec<Job> = Vec::new();
+
+    for e in load_answers(&days::dir(day).join(ANSWERS_FILE))? {
+        match jobs.iter_mut().find(|job| job.input == e.input) {
+            Some(job) => job.expected.push(e),
+            None => jobs.push(Job {
+                day,
+                input: e.input.clone(),
+                expected: vec![e],
+            }),
+        }
+    }
+
+    Ok(jobs)
+}
+
+impl Job {
+    /// Check every recorded answer, a panicking solver failing the checks
+    /// instead of the whole verification. Also returns how long it took.
+    pub fn run(&self) -> (Vec<Check>, Duration) {
+        let parts = self.expected.iter().map(|e| e.part).collect::<Vec<_>>();
+
+        let (results, elapsed) = common::timed(|| {
+            std::panic::catch_unwind(|| solve(self.day, &self.input, &parts)).unwrap_or_else(
+                |panic| {
+                    let message = panic
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| panic.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    parts
+                        .iter()
+                        .map(|_| Err(anyhow::anyhow!("the solver panicked: {message}")))
+                        .collect()
+                },
+            )
+        });
+
+        let checks = self
+            .expected
+            .iter()
+            .zip(results)
+            .map(|(expected, result)| Check {
+                day: self.day.number,
+                expected: expected.clone(),
+                status: match result {
+                    Ok(got) if got == expected.answer => Status::Pass,
+                    Ok(got) => Status::Regression { got },
+                    Err(e) => Status::Fail(e),
+                },
+            })
+            .collect();
+
+        (checks, elapsed)
+    }
+}
+
+/// Record the current answers of `day` for every input that has none yet,
+/// returning the newly recorded answers.
+pub fn record(day: &Day) -> anyhow::Result<Vec<Expected>> {
+    let path = days::dir(day).join(ANSWERS_FILE);
+    let expected = load_answers(&path)?;
+
+    let mut recorded = Vec::new();
+    for &input in RECORDED_INPUTS {
+        if !days::dir(day).join(input).exists() {
+            continue;
+        }
+
+        let missing = Part::ALL
+            .into_iter()
+            .filter(|&part| !expected.iter().any(|e| e.input == input && e.part == part))
+            .collect::<Vec<_>>();
+
+        for (part, answer) in missing.iter().zip(solve(day, input, &missing)) {
+            let answer = answer
+                .with_context(|| format!("day {} part {part} failed on {input}", day.number))?;
+            recorded.push(Expected {
+                input: input.to_owned(),
+                part: *part,
+                answer,
+            });
+        }
+    }
+
+    if !recorded.is_empty() {
+        let mut buf = match std::fs::read_to_string(&path) {
+            Ok(buf) => buf,
+            Err(_) => ANSWERS_HEADER.to_owned(),
+        };
+        if !buf.ends_with('\n') {
+            buf.push('\n');
+        }
+        for e in &recorded {
+            buf.push_str(&format!("{} {} {}\n", e.input, e.part, e.answer));
+        }
+        std::fs::write(&path, buf)
+            .with_context(|| format!("failed to write answers file `{}`", path.display()))?;
+    }
+
+    Ok(recorded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_file() {
+        let answers = parse_answers(&format!("{ANSWERS_HEADER}\ninput.txt 2 CMZ\n")).unwrap();
+
+        assert_eq!(
+            answers,
+            vec![Expected {
+                input: "input.txt".to_owned(),
+                part: Part::Two,
+                answer: "CMZ".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn panicking_solver_fails_its_checks() {
+        static PANICKING: Day = Day::of::<Panicking>();
+        let job = Job {
+            day: &PANICKING,
+            input: "example.input.txt".to_owned(),
+            expected: parse_answers("example.input.txt 1 42\nexample.input.txt 2 43").unwrap(),
+        };
+
+        let (checks, _) = job.run();
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(
+            |check| matches!(&check.status, Status::Fail(e) if e.to_string().contains("oops"))
+        ));
+    }
+
+    struct Panicking;
+
+    impl common::Solution for Panicking {
+        const DAY: u8 = 1;
+        const EXAMPLE: &'static str = "";
+
+        type Input = ();
+        type PartOne = u8;
+        type PartTwo = u8;
+
+        fn parse_with(_: &str, _: Mode) -> anyhow::Result<()> {
+            panic!("oops")
+        }
+
+        fn part_one(_: &()) -> anyhow::Result<u8> {
+            Ok(42)
+        }
+
+        fn part_two(_: &()) -> anyhow::Result<u8> {
+            Ok(43)
+        }
+    }
+
+    #[test]
+    fn invalid_answers_file() {
+        assert!(parse_answers("input.txt 3 42").is_err());
+        assert!(parse_answers("input.txt").is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{bail, Context};
+use common::{input::Source, parse::Mode, Day};
+
+use crate::{
+    days,
+    output::{format_duration, Record},
+};
+
+/// Last modification time of every watched file.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// The day's sources and the input it is run on.
+pub fn watched_paths(day: &Day, source: &Source) -> Vec<PathBuf> {
+    let dir = days::dir(day);
+    let input = match source {
+        Source::Path(path) => path.clone(),
+        Source::Example | Source::Stdin => dir.join("example.input.txt"),
+    };
+
+    vec![dir.join("src"), dir.join("Cargo.toml"), input]
+}
+
+/// Modification times of `paths`, walking directories. Missing files are
+/// left out, so that creating them counts as a change.
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut pending = paths.to_vec();
+
+    while let Some(path) = pending.pop() {
+        if let Ok(entries) = std::fs::read_dir(&path) {
+            pending.extend(entries.flatten().map(|entry| entry.path()));
+        } else if let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) {
+            snapshot.insert(path, modified);
+        }
+    }
+
+    snapshot
+}
+
+/// Files added, removed or modified between two snapshots.
+pub fn changes<'a>(before: &'a Snapshot, after: &'a Snapshot) -> Vec<&'a Path> {
+    let modified = after
+        .iter()
+        .filter(|(path, time)| before.get(*path) != Some(time))
+        .map(|(path, _)| path.as_path());
+    let removed = before
+        .keys()
+        .filter(|path| !after.contains_key(*path))
+        .map(PathBuf::as_path);
+
+    modified.chain(removed).collect()
+}
+
+/// Rebuild and run the day through cargo, so that source changes are picked
+/// up, reading back its JSON output.
+fn run(day: &Day, source: &Source, mode: Mode) -> anyhow::Result<Vec<Record>> {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+    let mut command = Command::new(cargo);
+    command
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(manifest)
+        .args(["--", "run", "--format", "json", "--day"])
+        .arg(day.number.to_string());
+    match source {
+        Source::Path(path) => command.arg(path),
+        Source::Example => command.arg("--example"),
+        Source::Stdin => bail!("cannot watch stdin"),
+    };
+    if mode == Mode::Strict {
+        command.arg("--strict");
+    }
+
+    let output = command.output().context("failed to run cargo")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim_end());
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(Record::from_json)
+        .collect()
+}
+
+/// Answers and timings of `current`, compared with the `previous` run.
+pub fn diff(previous: &[Record], current: &[Record]) -> String {
+    let mut out = String::new();
+
+    for record in current {
+        let before = previous.iter().find(|r| r.part == record.part);
+        let elapsed = format_duration(record.elapsed);
+
+        let line = match before {
+            None => format!("part {}: {}  {elapsed}", record.part, record.answer),
+            Some(before) => {
+                let answer = if before.answer == record.answer {
+                    format!("{} (unchanged)", record.answer)
+                } else {
+                    format!("{} -> {} (CHANGED)", before.answer, record.answer)
+                };
+                let change = match before.elapsed.as_secs_f64() {
+                    base if base > 0.0 => record.elapsed.as_secs_f64() / base - 1.0,
+                    _ => 0.0,
+                };
+                format!(
+                    "part {}: {answer}  {} -> {elapsed} ({:+.1}%)",
+                    record.part,
+                    format_duration(before.elapsed),
+                    change * 100.0
+                )
+            }
+        };
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Run the day, then again every time one of its files changes, checking
+/// for changes every `interval`. Never returns unless watching fails.
+pub fn watch(day: &Day, source: &Source, mode: Mode, interval: Duration) -> anyhow::Result<()> {
+    let paths = watched_paths(day, source);
+    let mut previous: Vec<Record> = Vec::new();
+    let mut seen = snapshot(&paths);
+
+    println!("watching day {} on {source}", day.number);
+    loop {
+        match run(day, source, mode) {
+            Ok(records) => {
+                print!("{}", diff(&previous, &records));
+                previous = records;
+            }
+            Err(e) => println!("day {} failed: {e:#}", day.number),
+        }
+
+        loop {
+            std::thread::sleep(interval);
+            let current = snapshot(&paths);
+            let changed = changes(&seen, &current);
+
+            if !changed.is_empty() {
+                let names = changed
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>();
+                println!("\nchanged: {}", names.join(", "));
+                seen = current;
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Part;
+
+    use super::*;
+
+    fn record(part: Part, answer: &str, micros: u64) -> Record {
+        Record {
+            day: 1,
+            part,
+            answer: answer.to_owned(),
+            elapsed: Duration::from_micros(micros),
+        }
+    }
+
+    #[test]
+    fn answers_and_timings() {
+        let previous = [
+            record(Part::One, "24000", 10),
+            record(Part::Two, "41000", 10),
+        ];
+        let current = [
+            record(Part::One, "24000", 5),
+            record(Part::Two, "45000", 20),
+        ];
+
+        assert_eq!(
+            diff(&previous, &current),
+            "part 1: 24000 (unchanged)  10.00µs -> 5.00µs (-50.0%)\n\
+             part 2: 41000 -> 45000 (CHANGED)  10.00µs -> 20.00µs (+100.0%)\n"
+        );
+        assert_eq!(diff(&[], &current[..1]), "part 1: 24000  5.00µs\n");
+    }
+
+    #[test]
+    fn detects_changes() {
+        let a = PathBuf::from("a");
+        let b = PathBuf::from("b");
+        let before = Snapshot::from([(a.clone(), SystemTime::UNIX_EPOCH)]);
+        let after = Snapshot::from([
+            (a.clone(), SystemTime::UNIX_EPOCH + Duration::from_secs(1)),
+            (b.clone(), SystemTime::UNIX_EPOCH),
+        ]);
+
+        assert!(changes(&before, &before).is_empty());
+        assert_eq!(changes(&before, &after), vec![a.as_path(), b.as_path()]);
+        assert_eq!(changes(&after, &before), vec![a.as_path(), b.as_path()]);
+    }
+}
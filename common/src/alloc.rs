@@ -0,0 +1,259 @@
+//! Allocator wrapper measuring how much memory each phase of a solver uses.
+//!
+//! Nothing is counted unless a binary opts in by installing [`Counting`] as
+//! its global allocator:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: Counting<System> = Counting::new(System);
+//! ```
+//!
+//! Counters are kept per thread, so that phases running on different threads
+//! do not see each other's allocations.
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const { Cell::new(Counters::ZERO) };
+}
+
+/// Allocations of the current phase of a thread. Memory may be freed by
+/// another thread than the one allocating it, hence signed counts.
+#[derive(Debug, Clone, Copy)]
+struct Counters {
+    allocations: usize,
+    live: isize,
+    bytes: isize,
+    peak_live: isize,
+    peak_bytes: isize,
+}
+
+impl Counters {
+    const ZERO: Counters = Counters {
+        allocations: 0,
+        live: 0,
+        bytes: 0,
+        peak_live: 0,
+        peak_bytes: 0,
+    };
+
+    fn update(f: impl FnOnce(&mut Counters)) {
+        // Counters are gone while the thread is being torn down.
+        let _ = COUNTERS.try_with(|counters| {
+            let mut current = counters.get();
+            f(&mut current);
+            current.peak_live = current.peak_live.max(current.live);
+            current.peak_bytes = current.peak_bytes.max(current.bytes);
+            counters.set(current);
+        });
+    }
+}
+
+/// `A`, counting every allocation made through it.
+pub struct Counting<A> {
+    inner: A,
+}
+
+impl<A> Counting<A> {
+    pub const fn new(inner: A) -> Self {
+        Counting { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Counting<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        Counters::update(|counters| {
+            counters.live -= 1;
+            counters.bytes -= layout.size() as isize;
+        });
+    }
+
+    /// Counted as one more allocation, growing or shrinking the live bytes.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = self.inner.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            Counters::update(|counters| {
+                counters.allocations += 1;
+                counters.bytes += new_size as isize - layout.size() as isize;
+            });
+        }
+        new
+    }
+}
+
+fn allocated(size: usize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    Counters::update(|counters| {
+        counters.allocations += 1;
+        counters.live += 1;
+        counters.bytes += size as isize;
+    });
+}
+
+/// Whether [`Counting`] is the global allocator, so that [`measure`] means
+/// anything.
+pub fn is_counting() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Memory used by a phase, on the thread running it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Usage {
+    /// Allocations and reallocations made.
+    pub allocations: usize,
+    /// Highest number of allocations alive at once.
+    pub peak_live: usize,
+    /// Highest number of bytes allocated at once.
+    pub peak_bytes: usize,
+    /// Allocations still alive at the end of the phase.
+    pub live: usize,
+    /// Bytes still allocated at the end of the phase.
+    pub bytes: usize,
+}
+
+impl Usage {
+    /// Usage of this phase followed by `next`, what this phase left alive
+    /// adding up to the peaks of `next`.
+    pub fn then(self, next: Usage) -> Usage {
+        Usage {
+            allocations: self.allocations + next.allocations,
+            peak_live: self.peak_live.max(self.live + next.peak_live),
+            peak_bytes: self.peak_bytes.max(self.bytes + next.peak_bytes),
+            live: self.live + next.live,
+            bytes: self.bytes + next.bytes,
+        }
+    }
+}
+
+/// Run `f`, returning its result along with the memory it used on the
+/// current thread. Measures can be nested.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let outer = COUNTERS.with(|counters| counters.replace(Counters::ZERO));
+    let out = f();
+    let inner = COUNTERS.with(Cell::get);
+
+    COUNTERS.with(|counters| {
+        counters.set(Counters {
+            allocations: outer.allocations + inner.allocations,
+            live: outer.live + inner.live,
+            bytes: outer.bytes + inner.bytes,
+            peak_live: outer.peak_live.max(outer.live + inner.peak_live),
+            peak_bytes: outer.peak_bytes.max(outer.bytes + inner.peak_bytes),
+        })
+    });
+
+    let clamp = |n: isize| n.max(0) as usize;
+    let usage = Usage {
+        allocations: inner.allocations,
+        peak_live: clamp(inner.peak_live),
+        peak_bytes: clamp(inner.peak_bytes),
+        live: clamp(inner.live),
+        bytes: clamp(inner.bytes),
+    };
+    (out, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::System;
+
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting<System> = Counting::new(System);
+
+    #[test]
+    fn counts_a_phase() {
+        assert!(is_counting());
+
+        let (kept, usage) = measure(|| {
+            let dropped = vec![0u8; 1000];
+            drop(dropped);
+            Box::new(0u64)
+        });
+
+        assert_eq!(usage.allocations, 2);
+        assert_eq!((usage.peak_live, usage.peak_bytes), (1, 1000));
+        assert_eq!((usage.live, usage.bytes), (1, 8));
+        drop(kept);
+    }
+
+    #[test]
+    fn nested_phases() {
+        let (_, outer) = measure(|| {
+            let kept = vec![0u8; 100];
+            let (_, inner) = measure(|| vec![0u8; 50]);
+            assert_eq!((inner.allocations, inner.peak_bytes), (1, 50));
+            kept
+        });
+
+        assert_eq!(outer.allocations, 2);
+        assert_eq!(outer.peak_bytes, 150);
+        assert_eq!(outer.bytes, 100);
+    }
+
+    #[test]
+    fn reallocations() {
+        let (_, usage) = measure(|| {
+            let mut v = vec![0u8; 10];
+            v.reserve_exact(90);
+            v
+        });
+
+        assert_eq!(usage.allocations, 2);
+        assert_eq!(usage.bytes, 100);
+    }
+
+    #[test]
+    fn phases_in_sequence() {
+        let parse = Usage {
+            allocations: 3,
+            peak_live: 3,
+            peak_bytes: 300,
+            live: 2,
+            bytes: 200,
+        };
+        let solve = Usage {
+            allocations: 1,
+            peak_live: 1,
+            peak_bytes: 50,
+            live: 0,
+            bytes: 0,
+        };
+
+        let total = parse.then(solve);
+        assert_eq!((total.peak_live, total.peak_bytes), (3, 300));
+        assert_eq!(
+            parse
+                .then(Usage {
+                    peak_bytes: 150,
+                    ..solve
+                })
+                .peak_bytes,
+            350
+        );
+    }
+}
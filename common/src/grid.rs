@@ -0,0 +1,392 @@
+//! Rectangular grids of cells, as found in map shaped puzzle inputs.
+
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::{Mode, ParseError, Scanner};
+
+/// Coordinates of a cell as `(x, y)`, `x` growing rightwards and `y`
+/// downwards from the top left corner.
+pub type Position = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The four directions along rows and columns, clockwise from up.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The eight directions, diagonals included, clockwise from up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Change of `(x, y)` for a single step.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::Right => Direction::Left,
+            Direction::DownRight => Direction::UpLeft,
+            Direction::Down => Direction::Up,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::Left => Direction::Right,
+            Direction::UpLeft => Direction::DownRight,
+        }
+    }
+}
+
+/// Cells stored row after row in a single vector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` columns whose rows follow each other in `cells`,
+    /// `None` when they do not fill the last row.
+    pub fn new(width: usize, cells: Vec<T>) -> Option<Self> {
+        match (width, cells.len()) {
+            (0, 0) => Some(Self::default()),
+            (0, _) => None,
+            (width, len) if len % width == 0 => Some(Grid {
+                width,
+                height: len / width,
+                cells,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Grid of the characters of `s`, one row per line, every cell being
+    /// converted by `cell` or reported as not being `expected`. Rows with a
+    /// bad cell, or not as wide as the first one, are skipped unless `mode`
+    /// is strict.
+    pub fn parse(
+        s: &str,
+        expected: &str,
+        mode: Mode,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(s.len());
+
+        for (idx, line) in s.lines().enumerate() {
+            let row = mode.line(idx, line, |line| {
+                let row = Scanner::whole(line, |s| {
+                    s.many(|s| {
+                        let start = *s;
+                        let c = s.char(expected, |_| true)?;
+                        cell(c).ok_or_else(|| start.error(expected))
+                    })
+                })?;
+
+                match width {
+                    Some(width) if width != row.len() => Err(ParseError::at_char(
+                        line,
+                        row.len().min(width),
+                        format!("a row of {width} cells"),
+                    )),
+                    _ => Ok(row),
+                }
+            })?;
+
+            if let Some(row) = row {
+                width.get_or_insert(row.len());
+                cells.extend(row);
+            }
+        }
+
+        Ok(Self::new(width.unwrap_or(0), cells).expect("every row has the same width"))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Position) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): Position) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Every position, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell along with its position, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` does not accept a width of 0, an empty grid has no row.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} out of a grid {} wide",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Position one step away from `position` towards `direction`, if it is
+    /// still inside the grid.
+    pub fn step(&self, (x, y): Position, direction: Direction) -> Option<Position> {
+        let (dx, dy) = direction.offset();
+        let x = x.checked_add_signed(dx).filter(|x| *x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|y| *y < self.height)?;
+        Some((x, y))
+    }
+
+    /// Positions from `position`, excluded, towards `direction` until the
+    /// edge of the grid.
+    pub fn ray(
+        &self,
+        position: Position,
+        direction: Direction,
+    ) -> impl Iterator<Item = Position> + '_ {
+        std::iter::successors(self.step(position, direction), move |position| {
+            self.step(*position, direction)
+        })
+    }
+
+    /// Neighbours sharing an edge with `position`.
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// Neighbours sharing an edge or a corner with `position`.
+    pub fn all_neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// Grid whose rows are the columns of this one.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{position:?} out of a grid of {}x{}",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} out of a grid of {width}x{height}"))
+    }
+}
+
+/// Every cell next to each other, one line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Grid<u32> {
+        Grid::parse(s, "a digit", Mode::Strict, |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = digits("123\n456\n");
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(digits(""), Grid::default());
+    }
+
+    #[test]
+    fn invalid_grid() {
+        let parse = |s| Grid::parse(s, "a digit", Mode::Strict, |c| c.to_digit(10)).unwrap_err();
+
+        assert_eq!((parse("12\n3x").line, parse("12\n3x").column), (2, 2));
+        assert_eq!((parse("12\n3").line, parse("12\n3").column), (2, 2));
+        assert_eq!(parse("12\n345").column, 3);
+    }
+
+    #[test]
+    fn lenient_grid() {
+        let parse = |s| Grid::parse(s, "a digit", Mode::Lenient, |c| c.to_digit(10)).unwrap();
+
+        assert_eq!(parse("12\n3x\n45\n678\n90"), digits("12\n45\n90"));
+    }
+
+    #[test]
+    fn blank_lines() {
+        assert_eq!(digits("123\n456\n\n"), digits("123\n456"));
+        assert_eq!(digits("123\r\n456\r\n\r\n\r\n"), digits("123\n456"));
+        assert_eq!(digits("\n\n"), Grid::default());
+        assert_eq!(digits("12\n\n34\n"), digits("12\n34"));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456");
+
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.transpose(), digits("14\n25\n36"));
+        assert_eq!(Grid::<u32>::default().rows().count(), 0);
+    }
+
+    #[test]
+    fn rays() {
+        let grid = digits("123\n456\n789");
+
+        assert_eq!(
+            grid.ray((1, 1), Direction::Left).collect::<Vec<_>>(),
+            vec![(0, 1)]
+        );
+        assert_eq!(
+            grid.ray((0, 0), Direction::DownRight).collect::<Vec<_>>(),
+            vec![(1, 1), (2, 2)]
+        );
+        assert_eq!(grid.ray((0, 0), Direction::Up).count(), 0);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = digits("123\n456\n789");
+
+        assert_eq!(grid.neighbours((0, 0)).count(), 2);
+        assert_eq!(grid.neighbours((1, 1)).count(), 4);
+        assert_eq!(grid.all_neighbours((1, 1)).count(), 8);
+        assert_eq!(
+            grid.all_neighbours((2, 0)).collect::<Vec<_>>(),
+            vec![(2, 1), (1, 1), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn opposite_directions_cancel() {
+        for direction in Direction::ALL {
+            let (dx, dy) = direction.offset();
+            let (ox, oy) = direction.opposite().offset();
+            assert_eq!((dx + ox, dy + oy), (0, 0));
+        }
+    }
+}
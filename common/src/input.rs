@@ -0,0 +1,215 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context};
+
+/// Input read when no source is given on the command line.
+pub const DEFAULT_INPUT_PATH: &str = "input.txt";
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Path(PathBuf),
+    /// `-` on the command line.
+    Stdin,
+    /// `--example`, the `example.input.txt` bundled in the day's crate.
+    Example,
+}
+
+impl Source {
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            "--example" => Source::Example,
+            path => Source::Path(PathBuf::from(path)),
+        }
+    }
+
+    /// Source given as the single argument of a day's binary, `input.txt`
+    /// when there is none.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut args = args.into_iter();
+
+        let source = args
+            .next()
+            .map(|arg| Self::from_arg(&arg))
+            .unwrap_or_else(|| Source::Path(PathBuf::from(DEFAULT_INPUT_PATH)));
+
+        if let Some(extra) = args.next() {
+            bail!("unexpected argument `{extra}`, expected a single input source");
+        }
+
+        Ok(source)
+    }
+
+    pub fn read(&self, example: &'static str) -> anyhow::Result<String> {
+        match self {
+            Source::Path(path) => read_file(path),
+            Source::Stdin => {
+                let mut buf = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut buf)
+                    .context("failed to read input from stdin")?;
+                Ok(buf)
+            }
+            Source::Example => Ok(example.to_owned()),
+        }
+    }
+
+    /// Open the source for reading, without loading it in memory.
+    pub fn open(&self, example: &'static str) -> anyhow::Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Source::Path(path) => {
+                Box::new(BufReader::new(File::open(path).with_context(|| {
+                    format!("failed to read input file `{}`", path.display())
+                })?))
+            }
+            Source::Stdin => Box::new(std::io::stdin().lock()),
+            Source::Example => Box::new(example.as_bytes()),
+        })
+    }
+}
+
+/// Call `f` on every line of `reader`, along with its 0-based index. A single
+/// buffer is reused, memory is only bounded by the longest line.
+pub fn for_each_line(
+    reader: &mut dyn BufRead,
+    mut f: impl FnMut(usize, &str) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let mut line = String::new();
+
+    for idx in 0.. {
+        line.clear();
+        if reader
+            .read_line(&mut line)
+            .context("failed to read input")?
+            == 0
+        {
+            break;
+        }
+
+        let line = line.strip_suffix('\n').unwrap_or(&line);
+        f(idx, line.strip_suffix('\r').unwrap_or(line))?;
+    }
+
+    Ok(())
+}
+
+/// Characters of `reader`, decoded one at a time.
+pub fn chars(reader: &mut dyn BufRead) -> impl Iterator<Item = anyhow::Result<char>> + '_ {
+    std::iter::from_fn(move || next_char(reader).transpose())
+}
+
+fn next_char(reader: &mut dyn BufRead) -> anyhow::Result<Option<char>> {
+    let mut bytes = [0; 4];
+    let mut len = 0;
+    let mut width = 1;
+
+    while len < width {
+        let Some(&byte) = reader.fill_buf().context("failed to read input")?.first() else {
+            return match len {
+                0 => Ok(None),
+                _ => Err(anyhow!("input ends in the middle of a character")),
+            };
+        };
+        reader.consume(1);
+
+        if len == 0 {
+            width = match byte {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+        }
+        bytes[len] = byte;
+        len += 1;
+    }
+
+    std::str::from_utf8(&bytes[..len])
+        .ok()
+        .and_then(|s| s.chars().next())
+        .map(Some)
+        .context("input is not valid UTF-8")
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+            Source::Example => write!(f, "example"),
+        }
+    }
+}
+
+fn read_file(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read input file `{}`", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn sources() {
+        assert_eq!(
+            Source::from_args(args(&[])).unwrap(),
+            Source::Path(PathBuf::from(DEFAULT_INPUT_PATH))
+        );
+        assert_eq!(Source::from_args(args(&["-"])).unwrap(), Source::Stdin);
+        assert_eq!(
+            Source::from_args(args(&["--example"])).unwrap(),
+            Source::Example
+        );
+        assert!(Source::from_args(args(&["a.txt", "b.txt"])).is_err());
+    }
+
+    #[test]
+    fn read_example() {
+        assert_eq!(Source::Example.read("1\n2\n").unwrap(), "1\n2\n");
+    }
+
+    #[test]
+    fn lines() {
+        let mut lines = Vec::new();
+        for_each_line(&mut "a\r\n\nb".as_bytes(), |idx, line| {
+            lines.push((idx, line.to_owned()));
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(
+            lines,
+            [(0, "a"), (1, ""), (2, "b")].map(|(i, l)| (i, l.to_owned()))
+        );
+    }
+
+    #[test]
+    fn decode_chars() {
+        let decode = |bytes: &[u8]| chars(&mut &bytes[..]).collect::<anyhow::Result<String>>();
+
+        assert_eq!(decode("aé€😀".as_bytes()).unwrap(), "aé€😀");
+        assert!(decode(&[b'a', 0xff]).is_err());
+        assert!(decode(&"é".as_bytes()[..1]).is_err());
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = Source::from_arg("does/not/exist.txt").read("").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "failed to read input file `does/not/exist.txt`"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+//! Just enough JSON for flat records, and arrays of them, without pulling a
+//! serializer.
+
+use std::{fmt::Write, iter::Peekable, str::Chars};
+
+use anyhow::{bail, Context};
+
+/// A JSON object built one field at a time.
+#[derive(Debug, Clone)]
+pub struct Object {
+    buf: String,
+}
+
+impl Object {
+    pub fn new() -> Self {
+        Object {
+            buf: String::from("{"),
+        }
+    }
+
+    pub fn string(mut self, key: &str, value: &str) -> Self {
+        self.key(key);
+        write_string(&mut self.buf, value);
+        self
+    }
+
+    pub fn number(mut self, key: &str, value: impl Into<u128>) -> Self {
+        self.key(key);
+        write!(self.buf, "{}", value.into()).expect("writing to a String cannot fail");
+        self
+    }
+
+    pub fn numbers<T: Into<u128>>(
+        mut self,
+        key: &str,
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.key(key);
+        self.buf.push('[');
+        for (idx, value) in values.into_iter().enumerate() {
+            if idx > 0 {
+                self.buf.push(',');
+            }
+            write!(self.buf, "{}", value.into()).expect("writing to a String cannot fail");
+        }
+        self.buf.push(']');
+        self
+    }
+
+    pub fn finish(mut self) -> String {
+        self.buf.push('}');
+        self.buf
+    }
+
+    fn key(&mut self, key: &str) {
+        if self.buf.len() > 1 {
+            self.buf.push(',');
+        }
+        write_string(&mut self.buf, key);
+        self.buf.push(':');
+    }
+}
+
+impl Default for Object {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Append `s` to `out` as a quoted and escaped JSON string.
+pub fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                write!(out, "\\u{:04x}", c as u32).expect("writing to a String cannot fail")
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// A field value of a flat object, as written by [`Object`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Number(u128),
+    Numbers(Vec<u128>),
+}
+
+/// Fields of a flat object, in order. Only the strings, unsigned numbers and
+/// arrays of them written by [`Object`] are supported.
+pub fn parse_object(s: &str) -> anyhow::Result<Vec<(String, Value)>> {
+    let mut chars = s.chars().peekable();
+    let fields = object(&mut chars)?;
+
+    if let Some(c) = skip_whitespace(&mut chars) {
+        bail!("unexpected {c:?} after the object");
+    }
+    Ok(fields)
+}
+
+/// Fields of each flat object of an array, as read by [`parse_object`].
+pub fn parse_objects(s: &str) -> anyhow::Result<Vec<Vec<(String, Value)>>> {
+    let mut chars = s.chars().peekable();
+
+    expect(&mut chars, '[')?;
+    let objects = separated(&mut chars, ']', object)?;
+
+    if let Some(c) = skip_whitespace(&mut chars) {
+        bail!("unexpected {c:?} after the array");
+    }
+    Ok(objects)
+}
+
+fn object(chars: &mut Peekable<Chars>) -> anyhow::Result<Vec<(String, Value)>> {
+    expect(chars, '{')?;
+    separated(chars, '}', |chars| {
+        skip_whitespace(chars);
+        let key = parse_string(chars)?;
+        expect(chars, ':')?;
+
+        let value = match skip_whitespace(chars) {
+            Some('"') => Value::String(parse_string(chars)?),
+            Some('[') => {
+                chars.next();
+                Value::Numbers(separated(chars, ']', |chars| {
+                    match skip_whitespace(chars) {
+                        Some(c) if c.is_ascii_digit() => parse_number(chars),
+                        other => bail!("expected a number, found {other:?}"),
+                    }
+                })?)
+            }
+            Some(c) if c.is_ascii_digit() => Value::Number(parse_number(chars)?),
+            other => bail!("expected a string, a number or an array, found {other:?}"),
+        };
+        Ok((key, value))
+    })
+}
+
+/// Items read by `item` up to the closing `end`, the opening delimiter being
+/// already consumed.
+fn separated<T>(
+    chars: &mut Peekable<Chars>,
+    end: char,
+    mut item: impl FnMut(&mut Peekable<Chars>) -> anyhow::Result<T>,
+) -> anyhow::Result<Vec<T>> {
+    let mut items = Vec::new();
+    if skip_whitespace(chars) == Some(end) {
+        chars.next();
+        return Ok(items);
+    }
+
+    loop {
+        items.push(item(chars)?);
+
+        match skip_whitespace(chars) {
+            Some(',') => {
+                chars.next();
+            }
+            Some(c) if c == end => {
+                chars.next();
+                return Ok(items);
+            }
+            other => bail!("expected `,` or `{end}`, found {other:?}"),
+        }
+    }
+}
+
+/// Skip whitespace, returning the next character without consuming it.
+fn skip_whitespace(chars: &mut Peekable<Chars>) -> Option<char> {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    chars.peek().copied()
+}
+
+fn expect(chars: &mut Peekable<Chars>, expected: char) -> anyhow::Result<()> {
+    match (skip_whitespace(chars), chars.next()) {
+        (_, Some(c)) if c == expected => Ok(()),
+        (_, found) => bail!("expected `{expected}`, found {found:?}"),
+    }
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> anyhow::Result<String> {
+    expect(chars, '"')?;
+    let mut out = String::new();
+
+    loop {
+        match chars.next().context("unterminated string")? {
+            '"' => return Ok(out),
+            '\\' => out.push(match chars.next().context("unterminated string")? {
+                '"' => '"',
+                '\\' => '\\',
+                '/' => '/',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    u32::from_str_radix(&code, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .with_context(|| format!("invalid escape `\\u{code}`"))?
+                }
+                c => bail!("invalid escape `\\{c}`"),
+            }),
+            c => out.push(c),
+        }
+    }
+}
+
+fn parse_number(chars: &mut Peekable<Chars>) -> anyhow::Result<u128> {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    digits
+        .parse()
+        .with_context(|| format!("invalid number `{digits}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object() {
+        let object = Object::new()
+            .number("day", 1u8)
+            .string("answer", "a \"quoted\"\nline")
+            .finish();
+
+        assert_eq!(object, r#"{"day":1,"answer":"a \"quoted\"\nline"}"#);
+    }
+
+    #[test]
+    fn empty_object() {
+        assert_eq!(Object::new().finish(), "{}");
+        assert_eq!(parse_object(" { } ").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let object = Object::new()
+            .number("day", 1u8)
+            .string("answer", "a \"quoted\"\nline\u{1}")
+            .finish();
+
+        assert_eq!(
+            parse_object(&object).unwrap(),
+            vec![
+                ("day".to_owned(), Value::Number(1)),
+                (
+                    "answer".to_owned(),
+                    Value::String("a \"quoted\"\nline\u{1}".to_owned())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        let object = Object::new()
+            .numbers("foods", [1000u16, 2000])
+            .numbers("none", [0u8; 0])
+            .finish();
+        assert_eq!(object, r#"{"foods":[1000,2000],"none":[]}"#);
+
+        assert_eq!(
+            parse_objects(&format!(" [\n{object},\n{{}} ]\n")).unwrap(),
+            vec![
+                vec![
+                    ("foods".to_owned(), Value::Numbers(vec![1000, 2000])),
+                    ("none".to_owned(), Value::Numbers(vec![])),
+                ],
+                vec![],
+            ]
+        );
+        assert_eq!(parse_objects("[]").unwrap(), Vec::<Vec<_>>::new());
+    }
+
+    #[test]
+    fn malformed_objects() {
+        for object in [
+            r#"{"a":1"#,
+            r#"{"a":-1}"#,
+            r#"{"a" 1}"#,
+            r#"{"a":"\x"}"#,
+            r#"{} {}"#,
+            r#"{"a":[1,]}"#,
+            r#"{"a":["1"]}"#,
+        ] {
+            assert!(parse_object(object).is_err(), "{object}");
+        }
+
+        for array in ["[{}", "[{},]", "[1]", "{}", "[] []"] {
+            assert!(parse_objects(array).is_err(), "{array}");
+        }
+    }
+}
@@ -0,0 +1,275 @@
+pub mod alloc;
+pub mod generator;
+pub mod grid;
+pub mod input;
+pub mod json;
+pub mod parse;
+pub mod visual;
+
+use std::{
+    any::Any,
+    fmt::Display,
+    io::BufRead,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use generator::{Generator, Rng};
+use input::Source;
+use parse::Mode;
+use visual::{Animation, Visualize};
+
+/// One of the two halves of a daily puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(anyhow::anyhow!("invalid part `{s}`, expected 1 or 2")),
+        }
+    }
+}
+
+/// A day's puzzle: the input is parsed once and shared by both parts.
+pub trait Solution {
+    const DAY: u8;
+    /// Content of the day's `example.input.txt`.
+    const EXAMPLE: &'static str;
+
+    type Input: 'static;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    /// Parse the input, skipping malformed lines where the puzzle allows it.
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Self::parse_with(input, Mode::Lenient)
+    }
+
+    fn parse_with(input: &str, mode: Mode) -> anyhow::Result<Self::Input>;
+
+    fn part_one(input: &Self::Input) -> anyhow::Result<Self::PartOne>;
+
+    fn part_two(input: &Self::Input) -> anyhow::Result<Self::PartTwo>;
+}
+
+/// A [`Solution`] able to solve both parts in a single pass over its input,
+/// with memory bounded whatever the size of the input.
+pub trait Streaming: Solution {
+    fn stream(
+        reader: &mut dyn BufRead,
+        mode: Mode,
+    ) -> anyhow::Result<(Self::PartOne, Self::PartTwo)>;
+}
+
+/// Read the input of `S` from the source given on the command line.
+pub fn read_input<S: Solution>() -> anyhow::Result<String> {
+    Source::from_args(std::env::args().skip(1))?.read(S::EXAMPLE)
+}
+
+/// Tests checking the answers of both parts of a [`Solution`] on its
+/// example, each of them given the attributes listed first.
+///
+/// ```ignore
+/// common::example_tests!(Day2, 15, 12);
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($(#[$attr:meta])* $day:ty, $one:expr, $two:expr $(,)?) => {
+        #[test]
+        $(#[$attr])*
+        fn example_part_one() {
+            let input = <$day as $crate::Solution>::parse(<$day as $crate::Solution>::EXAMPLE);
+
+            assert_eq!(<$day as $crate::Solution>::part_one(&input.unwrap()).unwrap(), $one);
+        }
+
+        #[test]
+        $(#[$attr])*
+        fn example_part_two() {
+            let input = <$day as $crate::Solution>::parse(<$day as $crate::Solution>::EXAMPLE);
+
+            assert_eq!(<$day as $crate::Solution>::part_two(&input.unwrap()).unwrap(), $two);
+        }
+    };
+}
+
+/// Run `f`, returning its result along with how long it took.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let out = f();
+    (out, start.elapsed())
+}
+
+/// Input parsed by a [`Day`], to be handed back to the same day.
+pub struct Parsed {
+    day: u8,
+    input: Box<dyn Any>,
+}
+
+/// Answers of both parts, read in a single pass.
+type StreamFn = fn(&mut dyn BufRead, Mode) -> anyhow::Result<(String, String)>;
+
+/// Replay of a part, borrowing the parsed input.
+type VisualizeFn = for<'a> fn(&'a dyn Any, Part) -> anyhow::Result<Box<dyn Animation + 'a>>;
+
+/// Type-erased [`Solution`], so every day fits in a single table.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub example: &'static str,
+    parse: fn(&str, Mode) -> anyhow::Result<Box<dyn Any>>,
+    part_one: fn(&dyn Any) -> anyhow::Result<String>,
+    part_two: fn(&dyn Any) -> anyhow::Result<String>,
+    generate: Option<fn(&mut Rng, usize) -> String>,
+    stream: Option<StreamFn>,
+    visualize: Option<VisualizeFn>,
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Self {
+        Day {
+            number: S::DAY,
+            example: S::EXAMPLE,
+            parse: parse::<S>,
+            part_one: part_one::<S>,
+            part_two: part_two::<S>,
+            generate: None,
+            stream: None,
+            visualize: None,
+        }
+    }
+
+    pub const fn with_generator<G: Generator>(mut self) -> Self {
+        self.generate = Some(G::generate);
+        self
+    }
+
+    pub const fn with_streaming<S: Streaming>(mut self) -> Self {
+        self.stream = Some(stream::<S>);
+        self
+    }
+
+    pub const fn with_visualizer<V: Visualize + 'static>(mut self) -> Self {
+        self.visualize = Some(visualize::<V>);
+        self
+    }
+
+    pub fn parse(&self, input: &str, mode: Mode) -> anyhow::Result<Parsed> {
+        Ok(Parsed {
+            day: self.number,
+            input: (self.parse)(input, mode)?,
+        })
+    }
+
+    pub fn solve(&self, parsed: &Parsed, part: Part) -> anyhow::Result<String> {
+        anyhow::ensure!(
+            parsed.day == self.number,
+            "input parsed by day {} given to day {}",
+            parsed.day,
+            self.number
+        );
+
+        match part {
+            Part::One => (self.part_one)(parsed.input.as_ref()),
+            Part::Two => (self.part_two)(parsed.input.as_ref()),
+        }
+    }
+
+    /// Random input of the given size, if the day has a generator.
+    pub fn generate(&self, rng: &mut Rng, size: usize) -> anyhow::Result<String> {
+        let generate = self
+            .generate
+            .with_context(|| format!("day {} has no input generator", self.number))?;
+        Ok(generate(rng, size))
+    }
+
+    /// Answers of both parts, read in a single pass over `reader`, if the day
+    /// can stream its input.
+    pub fn stream(&self, reader: &mut dyn BufRead, mode: Mode) -> anyhow::Result<(String, String)> {
+        let stream = self
+            .stream
+            .with_context(|| format!("day {} cannot stream its input", self.number))?;
+        stream(reader, mode)
+    }
+
+    /// Step by step replay of `part` solved on `parsed`, if the day has a
+    /// visualizer.
+    pub fn visualize<'a>(
+        &self,
+        parsed: &'a Parsed,
+        part: Part,
+    ) -> anyhow::Result<Box<dyn Animation + 'a>> {
+        let visualize = self
+            .visualize
+            .with_context(|| format!("day {} has no visualizer", self.number))?;
+        anyhow::ensure!(
+            parsed.day == self.number,
+            "input parsed by day {} given to day {}",
+            parsed.day,
+            self.number
+        );
+        visualize(parsed.input.as_ref(), part)
+    }
+}
+
+impl std::fmt::Debug for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Day").field("number", &self.number).finish()
+    }
+}
+
+fn parse<S: Solution>(input: &str, mode: Mode) -> anyhow::Result<Box<dyn Any>> {
+    Ok(Box::new(S::parse_with(input, mode)?))
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> anyhow::Result<&S::Input> {
+    input
+        .downcast_ref()
+        .with_context(|| format!("input was not parsed by day {}", S::DAY))
+}
+
+fn part_one<S: Solution>(input: &dyn Any) -> anyhow::Result<String> {
+    Ok(S::part_one(downcast::<S>(input)?)?.to_string())
+}
+
+fn part_two<S: Solution>(input: &dyn Any) -> anyhow::Result<String> {
+    Ok(S::part_two(downcast::<S>(input)?)?.to_string())
+}
+
+fn stream<S: Streaming>(reader: &mut dyn BufRead, mode: Mode) -> anyhow::Result<(String, String)> {
+    let (one, two) = S::stream(reader, mode)?;
+    Ok((one.to_string(), two.to_string()))
+}
+
+fn visualize<V: Visualize + 'static>(
+    input: &dyn Any,
+    part: Part,
+) -> anyhow::Result<Box<dyn Animation + '_>> {
+    visual::record::<V>(downcast::<V>(input)?, part)
+}
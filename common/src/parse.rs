@@ -0,0 +1,448 @@
+//! Errors pointing at the exact place a puzzle input is malformed.
+
+use std::fmt::Display;
+
+/// What to do with a malformed line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Skip the line and keep going.
+    #[default]
+    Lenient,
+    /// Abort on the first malformed line.
+    Strict,
+}
+
+impl Mode {
+    /// Parse every line of `lines`, given with their 0-based index in the
+    /// input, skipping the malformed ones unless strict. Blank lines are
+    /// always skipped.
+    pub fn collect<'a, T>(
+        self,
+        lines: impl IntoIterator<Item = (usize, &'a str)>,
+        mut parse: impl FnMut(&'a str) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut out = Vec::new();
+
+        for (idx, line) in lines {
+            out.extend(self.line(idx, line, &mut parse)?);
+        }
+
+        Ok(out)
+    }
+
+    /// Parse a single line, given with its 0-based index in the input, as
+    /// [`Mode::collect`] does: `None` when the line is blank or skipped.
+    pub fn line<'a, T>(
+        self,
+        idx: usize,
+        line: &'a str,
+        parse: impl FnOnce(&'a str) -> Result<T, ParseError>,
+    ) -> Result<Option<T>, ParseError> {
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+
+        match parse(line) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if self == Mode::Strict => Err(e.on_line(idx + 1)),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+/// A malformed input line, rendered with a caret under the offending column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number in the input.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The offending line.
+    pub text: String,
+    /// Shape the input should have had at this column.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error at byte `offset` of `text`, on the first line until told
+    /// otherwise with [`ParseError::on_line`].
+    pub fn new(text: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(text.len());
+        let column = text
+            .char_indices()
+            .take_while(|(idx, _)| *idx < offset)
+            .count()
+            + 1;
+
+        ParseError {
+            line: 1,
+            column,
+            text: text.to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error at the 0-based character `index` of `text`.
+    pub fn at_char(text: &str, index: usize, expected: impl Into<String>) -> Self {
+        let offset = text
+            .char_indices()
+            .nth(index)
+            .map_or(text.len(), |(offset, _)| offset);
+        Self::new(text, offset, expected)
+    }
+
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = self.line.to_string().len();
+
+        writeln!(
+            f,
+            "expected {} at line {}, column {}",
+            self.expected, self.line, self.column
+        )?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(f, "{:gutter$} | {:>column$}", "", "^", column = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line read from left to right, every step failing with an error
+/// pointing at where the line stopped having the expected shape.
+#[derive(Debug, Clone, Copy)]
+pub struct Scanner<'a> {
+    line: &'a str,
+    offset: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Self::at(line, 0)
+    }
+
+    /// Scanner starting at byte `offset` of `line`.
+    pub fn at(line: &'a str, offset: usize) -> Self {
+        Scanner {
+            line,
+            offset: offset.min(line.len()),
+        }
+    }
+
+    /// Parse the whole of `line` with `parse`, nothing but whitespace being
+    /// allowed after it.
+    pub fn whole<T>(
+        line: &'a str,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let mut scanner = Self::new(line);
+        let value = parse(&mut scanner)?;
+        scanner.end()?;
+        Ok(value)
+    }
+
+    /// Byte offset of what is left to read.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.offset..]
+    }
+
+    /// Error at the current offset.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.line, self.offset, expected)
+    }
+
+    /// Skip whitespace, returning how many bytes were skipped.
+    pub fn whitespace(&mut self) -> usize {
+        let rest = self.rest();
+        let skipped = rest.len() - rest.trim_start().len();
+        self.offset += skipped;
+        skipped
+    }
+
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if !self.rest().starts_with(literal) {
+            return Err(self.error(format!("`{literal}`")));
+        }
+        self.offset += literal.len();
+        Ok(())
+    }
+
+    /// A single character satisfying `accept`.
+    pub fn char(
+        &mut self,
+        expected: &str,
+        accept: impl FnOnce(char) -> bool,
+    ) -> Result<char, ParseError> {
+        match self.rest().chars().next() {
+            Some(c) if accept(c) => {
+                self.offset += c.len_utf8();
+                Ok(c)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    /// Unsigned integer written with ASCII digits, failing at its first
+    /// digit when it does not fit in `T`.
+    pub fn unsigned<T: std::str::FromStr>(&mut self, expected: &str) -> Result<T, ParseError> {
+        let rest = self.rest();
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+
+        let value = rest[..digits].parse().map_err(|_| self.error(expected))?;
+        self.offset += digits;
+        Ok(value)
+    }
+
+    /// Next whitespace separated word, which must not be glued to what was
+    /// read before it, skipping the whitespace following it.
+    pub fn word(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        let boundary = self.line[..self.offset]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace);
+        if self.whitespace() == 0 && !boundary {
+            return Err(self.error(expected));
+        }
+
+        let rest = self.rest();
+        let word = &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())];
+        if word.is_empty() {
+            return Err(self.error(expected));
+        }
+        self.offset += word.len();
+        self.whitespace();
+        Ok(word)
+    }
+
+    /// The word `keyword` and nothing else.
+    pub fn keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        self.one_of(&[(keyword, ())], &format!("`{keyword}`"))
+    }
+
+    /// Value of the next word, which must be one of the `choices`.
+    pub fn one_of<T: Copy>(
+        &mut self,
+        choices: &[(&str, T)],
+        expected: &str,
+    ) -> Result<T, ParseError> {
+        let start = *self;
+        let word = self.word(expected)?;
+
+        match choices.iter().find(|(choice, _)| *choice == word) {
+            Some((_, value)) => Ok(*value),
+            None => {
+                *self = start;
+                self.whitespace();
+                Err(self.error(expected))
+            }
+        }
+    }
+
+    /// One or more `item`, separated by `separator`.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![item(self)?];
+
+        while self.rest().starts_with(separator) {
+            self.offset += separator.len();
+            items.push(item(self)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Items read by `item` until nothing is left of the line.
+    pub fn many<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = Vec::new();
+
+        while !self.rest().is_empty() {
+            items.push(item(self)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Nothing but whitespace left.
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        self.whitespace();
+        match self.rest() {
+            "" => Ok(()),
+            _ => Err(self.error("the end of the line")),
+        }
+    }
+}
+
+/// Lines of `s` grouped in blocks separated by blank lines, each line along
+/// with its 0-based index in `s`. Every blank line ends a block, so that
+/// consecutive ones give empty blocks, except for the one ending `s`.
+pub fn blocks(s: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut blocks = vec![Vec::new()];
+
+    for (idx, line) in s.lines().enumerate() {
+        match blocks.last_mut() {
+            Some(block) if !line.trim().is_empty() => block.push((idx, line)),
+            _ => blocks.push(Vec::new()),
+        }
+    }
+
+    if blocks.last().is_some_and(Vec::is_empty) {
+        blocks.pop();
+    }
+    blocks
+}
+
+/// Columns of `width` characters of `line`, the last one being shorter when
+/// `line` does not fill it, along with their byte offset.
+///
+/// # Panics
+///
+/// If `width` is 0.
+pub fn columns(line: &str, width: usize) -> impl Iterator<Item = (usize, &str)> {
+    let starts = line.char_indices().step_by(width).map(|(offset, _)| offset);
+    let ends = starts.clone().skip(1).chain(std::iter::once(line.len()));
+
+    starts
+        .zip(ends)
+        .map(move |(start, end)| (start, &line[start..end]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caret_diagnostic() {
+        let err = ParseError::new("2-x,4-5", 2, "a section number").on_line(12);
+
+        assert_eq!(
+            err.to_string(),
+            "expected a section number at line 12, column 3\n   |\n12 | 2-x,4-5\n   |   ^"
+        );
+    }
+
+    #[test]
+    fn column_counts_characters() {
+        assert_eq!(ParseError::new("éé x", 5, "").column, 4);
+    }
+
+    #[test]
+    fn lenient_skips_and_strict_aborts() {
+        let input = "1\nx\n3\n\n";
+        let parse = |line: &str| {
+            line.parse::<u32>()
+                .map_err(|_| ParseError::new(line, 0, "a number"))
+        };
+
+        assert_eq!(
+            Mode::Lenient.collect(input.lines().enumerate(), parse),
+            Ok(vec![1, 3])
+        );
+        assert_eq!(
+            Mode::Strict
+                .collect(input.lines().enumerate(), parse)
+                .unwrap_err()
+                .line,
+            2
+        );
+    }
+
+    #[test]
+    fn scanning() {
+        let range = |s: &mut Scanner| -> Result<(u32, u32), ParseError> {
+            let start = s.unsigned("a bound")?;
+            s.literal("-")?;
+            Ok((start, s.unsigned("a bound")?))
+        };
+
+        assert_eq!(
+            Scanner::whole("2-4,6-8", |s| s.separated(",", range)),
+            Ok(vec![(2, 4), (6, 8)])
+        );
+        assert_eq!(
+            Scanner::whole("2-4,6-x", |s| s.separated(",", range))
+                .unwrap_err()
+                .column,
+            7
+        );
+        assert_eq!(
+            Scanner::whole("2-4 x", range).unwrap_err().expected,
+            "the end of the line"
+        );
+        assert_eq!(Scanner::whole("99999999999", range).unwrap_err().column, 1);
+    }
+
+    #[test]
+    fn keywords() {
+        let movement = |s: &mut Scanner| -> Result<u32, ParseError> {
+            s.keyword("move")?;
+            s.unsigned("a number")
+        };
+
+        assert_eq!(Scanner::whole("  move 3", movement), Ok(3));
+        assert_eq!(Scanner::whole("mov 3", movement).unwrap_err().column, 1);
+        assert_eq!(Scanner::whole("movers 3", movement).unwrap_err().column, 1);
+        assert_eq!(Scanner::whole("move", movement).unwrap_err().column, 5);
+
+        let mut s = Scanner::new("1from");
+        s.unsigned::<u32>("a number").unwrap();
+        assert_eq!(s.keyword("from").unwrap_err().column, 2);
+    }
+
+    #[test]
+    fn every_character() {
+        let digit = |s: &mut Scanner| s.char("a digit", |c| c.is_ascii_digit());
+
+        assert_eq!(Scanner::whole("12", |s| s.many(digit)), Ok(vec!['1', '2']));
+        assert_eq!(Scanner::whole("", |s| s.many(digit)), Ok(vec![]));
+        assert_eq!(
+            Scanner::whole("1 2", |s| s.many(digit)).unwrap_err().column,
+            2
+        );
+    }
+
+    #[test]
+    fn choices() {
+        let choices = [("A", 1), ("B", 2)];
+        let mut s = Scanner::new("B  C");
+
+        assert_eq!(s.one_of(&choices, "A or B"), Ok(2));
+        assert_eq!(s.one_of(&choices, "A or B").unwrap_err().column, 4);
+        assert_eq!(s.char("a letter", char::is_alphabetic), Ok('C'));
+    }
+
+    #[test]
+    fn blank_line_separated_blocks() {
+        assert_eq!(
+            blocks("a\nb\n\n\nc\n\n"),
+            vec![vec![(0, "a"), (1, "b")], vec![], vec![(4, "c")]]
+        );
+        assert!(blocks("").is_empty());
+    }
+
+    #[test]
+    fn fixed_width_columns() {
+        assert_eq!(
+            columns("[A]     [é]", 4).collect::<Vec<_>>(),
+            vec![(0, "[A] "), (4, "    "), (8, "[é]")]
+        );
+        assert_eq!(columns("", 4).count(), 0);
+    }
+}
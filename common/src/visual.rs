@@ -0,0 +1,329 @@
+//! Step by step replay of a solver in the terminal, drawn with ANSI escape
+//! codes.
+
+use std::{
+    fmt::Display,
+    io::Write,
+    str::FromStr,
+    sync::mpsc::{self, RecvTimeoutError},
+    time::Duration,
+};
+
+use anyhow::{bail, Context};
+
+use crate::{Part, Solution};
+
+/// Clear the screen and move the cursor to its top left corner.
+pub const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// A solver whose execution can be replayed step by step.
+pub trait Visualize: Solution {
+    /// What happened at a step, enough for [`Visualize::render`] to draw it.
+    type Event;
+
+    /// Every step taken by the solver of `part` on `input`, in order.
+    fn events(input: &Self::Input, part: Part) -> anyhow::Result<Vec<Self::Event>>;
+
+    /// Draw the puzzle as it is right after `event`.
+    fn render(input: &Self::Input, event: &Self::Event) -> String;
+}
+
+/// Frames ready to be drawn, whatever the day they come from.
+pub trait Animation {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Draw the 0-based `frame`.
+    fn render(&self, frame: usize) -> String;
+}
+
+struct Recording<'a, V: Visualize> {
+    input: &'a V::Input,
+    events: Vec<V::Event>,
+}
+
+impl<V: Visualize> Animation for Recording<'_, V> {
+    fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    fn render(&self, frame: usize) -> String {
+        V::render(self.input, &self.events[frame])
+    }
+}
+
+/// Record the events of the solver of `part` on `input`.
+pub fn record<V: Visualize + 'static>(
+    input: &V::Input,
+    part: Part,
+) -> anyhow::Result<Box<dyn Animation + '_>> {
+    Ok(Box::new(Recording::<V> {
+        input,
+        events: V::events(input, part)?,
+    }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Cyan,
+    /// Swap the foreground and background colors.
+    Reverse,
+}
+
+/// `text` drawn in `color`.
+pub fn paint(text: impl Display, color: Color) -> String {
+    let code = match color {
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Cyan => 36,
+        Color::Reverse => 7,
+    };
+    format!("\x1b[{code}m{text}\x1b[0m")
+}
+
+/// A command typed to the player, one per line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// An empty line or `n`.
+    Next,
+    /// `b`.
+    Back,
+    /// `p`, play or pause.
+    Toggle,
+    /// `+`.
+    Faster,
+    /// `-`.
+    Slower,
+    /// `g <N>` or just `<N>`, the frame being 1-based.
+    Jump(usize),
+    /// `q`.
+    Quit,
+}
+
+impl FromStr for Control {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let frame = s.strip_prefix('g').map_or(s, str::trim);
+
+        match s {
+            "" | "n" => Ok(Control::Next),
+            "b" => Ok(Control::Back),
+            "p" => Ok(Control::Toggle),
+            "+" => Ok(Control::Faster),
+            "-" => Ok(Control::Slower),
+            "q" => Ok(Control::Quit),
+            _ => match frame.parse() {
+                Ok(frame) => Ok(Control::Jump(frame)),
+                Err(_) => bail!("unknown command `{s}`"),
+            },
+        }
+    }
+}
+
+/// Frames per second a replay starts at, unless told otherwise.
+pub const DEFAULT_FPS: f64 = 4.0;
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 240.0;
+
+/// Where the replay stands, apart from any terminal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    /// 0-based frame on screen.
+    pub frame: usize,
+    pub frames: usize,
+    pub playing: bool,
+    /// Frames per second while playing.
+    pub fps: f64,
+}
+
+impl Player {
+    /// Paused player on the first of `frames`, `fps` being brought within
+    /// the supported speeds and replaced by [`DEFAULT_FPS`] when NaN.
+    pub fn new(frames: usize, fps: f64) -> Self {
+        Player {
+            frame: 0,
+            frames,
+            playing: false,
+            fps: if fps.is_nan() {
+                DEFAULT_FPS
+            } else {
+                fps.clamp(MIN_FPS, MAX_FPS)
+            },
+        }
+    }
+
+    pub fn apply(&mut self, control: Control) {
+        let last = self.frames.saturating_sub(1);
+
+        match control {
+            Control::Next => {
+                self.playing = false;
+                self.frame = (self.frame + 1).min(last);
+            }
+            Control::Back => {
+                self.playing = false;
+                self.frame = self.frame.saturating_sub(1);
+            }
+            Control::Toggle => {
+                // Playing again from the last frame starts over.
+                if !self.playing && self.frame == last {
+                    self.frame = 0;
+                }
+                self.playing = !self.playing;
+            }
+            Control::Faster => self.fps = (self.fps * 2.0).min(MAX_FPS),
+            Control::Slower => self.fps = (self.fps / 2.0).max(MIN_FPS),
+            Control::Jump(frame) => self.frame = frame.saturating_sub(1).min(last),
+            Control::Quit => self.playing = false,
+        }
+    }
+
+    /// Move to the next frame while playing, pausing on the last one.
+    pub fn tick(&mut self) {
+        if self.frame + 1 < self.frames {
+            self.frame += 1;
+        } else {
+            self.playing = false;
+        }
+    }
+
+    pub fn delay(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps)
+    }
+
+    pub fn status(&self) -> String {
+        format!(
+            "frame {}/{} | {} at {} fps | [enter/n]ext [b]ack [p]lay/pause [+/-] speed [g N] jump [q]uit",
+            self.frame + 1,
+            self.frames,
+            if self.playing { "playing" } else { "paused" },
+            self.fps
+        )
+    }
+}
+
+/// Replay `animation` in the terminal, driven by the commands typed on
+/// stdin, until `q` or the end of stdin.
+pub fn play(animation: &dyn Animation, fps: f64) -> anyhow::Result<()> {
+    if animation.is_empty() {
+        bail!("nothing to visualize");
+    }
+
+    // Lines are read on their own thread so playing does not wait for them.
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut player = Player::new(animation.len(), fps);
+    let mut message = None;
+    let mut closed = false;
+    let mut out = std::io::stdout().lock();
+
+    loop {
+        write!(out, "{CLEAR}{}", animation.render(player.frame))?;
+        writeln!(out, "\n{}", player.status())?;
+        if let Some(message) = message.take() {
+            writeln!(out, "{}", paint(message, Color::Red))?;
+        }
+        out.flush().context("failed to draw the frame")?;
+
+        let line = match (player.playing, closed) {
+            (true, false) => match receiver.recv_timeout(player.delay()) {
+                Ok(line) => Some(line),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => {
+                    closed = true;
+                    None
+                }
+            },
+            (true, true) => {
+                std::thread::sleep(player.delay());
+                None
+            }
+            (false, false) => match receiver.recv() {
+                Ok(line) => Some(line),
+                Err(_) => return Ok(()),
+            },
+            (false, true) => return Ok(()),
+        };
+
+        match line.map(|line| line.parse()) {
+            None => player.tick(),
+            Some(Ok(Control::Quit)) => return Ok(()),
+            Some(Ok(control)) => player.apply(control),
+            Some(Err(e)) => message = Some(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn controls() {
+        assert_eq!("".parse::<Control>().unwrap(), Control::Next);
+        assert_eq!(" b ".parse::<Control>().unwrap(), Control::Back);
+        assert_eq!("g 12".parse::<Control>().unwrap(), Control::Jump(12));
+        assert_eq!("7".parse::<Control>().unwrap(), Control::Jump(7));
+        assert!("x".parse::<Control>().is_err());
+    }
+
+    #[test]
+    fn stepping() {
+        let mut player = Player::new(3, 4.0);
+
+        player.apply(Control::Back);
+        assert_eq!(player.frame, 0);
+        player.apply(Control::Jump(10));
+        assert_eq!(player.frame, 2);
+        player.apply(Control::Next);
+        assert_eq!(player.frame, 2);
+        player.apply(Control::Jump(2));
+        assert_eq!(player.frame, 1);
+    }
+
+    #[test]
+    fn playing() {
+        let mut player = Player::new(2, 4.0);
+
+        player.apply(Control::Toggle);
+        player.tick();
+        assert_eq!((player.frame, player.playing), (1, true));
+        player.tick();
+        assert_eq!((player.frame, player.playing), (1, false));
+
+        // Playing from the end starts over.
+        player.apply(Control::Toggle);
+        assert_eq!((player.frame, player.playing), (0, true));
+        player.apply(Control::Next);
+        assert!(!player.playing);
+    }
+
+    #[test]
+    fn speed() {
+        let mut player = Player::new(2, 1000.0);
+        assert_eq!(player.fps, MAX_FPS);
+
+        player.apply(Control::Slower);
+        assert_eq!(player.delay(), Duration::from_secs_f64(1.0 / 120.0));
+
+        assert_eq!(Player::new(2, f64::NAN).fps, DEFAULT_FPS);
+        assert_eq!(Player::new(2, f64::NEG_INFINITY).fps, MIN_FPS);
+    }
+}
@@ -0,0 +1,47 @@
+use common::generator::{Generator, Rng};
+
+use crate::Day1;
+
+impl Generator for Day1 {
+    /// `size` elves, each carrying between 1 and 15 foods. Nothing at all
+    /// when there are no elves.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let elves = (0..size)
+            .map(|_| {
+                (0..rng.range(1..=15))
+                    .map(|_| rng.range(1_000..=70_000).to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>();
+
+        if elves.is_empty() {
+            return String::new();
+        }
+        elves.join("\n\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{parse::Mode, Solution};
+
+    use super::*;
+
+    #[test]
+    fn generated_input_is_valid() {
+        let mut rng = Rng::new(1);
+
+        for size in [0, 3, 10, 100] {
+            let input = Day1::generate(&mut rng, size);
+            let elves = Day1::parse_with(&input, Mode::Strict).unwrap();
+
+            assert_eq!(elves.len(), size);
+            if size == 0 {
+                assert_eq!(input, "");
+                continue;
+            }
+            assert!(Day1::part_two(&elves).unwrap() >= Day1::part_one(&elves).unwrap());
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Elves written out as CSV or JSON and read back, along with the puzzle
+//! format they came from.
+//!
+//! CSV has a record per food item, and a record without item for each elf
+//! carrying none, so that empty elves survive the trip. JSON is an array of
+//! elves, each an object listing its `foods`.
+
+use std::fmt::Write;
+
+use anyhow::{bail, Context};
+use common::{
+    json::{self, Value},
+    parse::{ParseError, Scanner},
+};
+
+use crate::Elf;
+
+pub const CSV_HEADER: &str = "elf_index,item_index,calories";
+
+/// Format an inventory is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    /// An array of `{"foods":[...]}` objects, an elf per line.
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => bail!("unknown inventory format `{s}`, expected `csv` or `json`"),
+        }
+    }
+}
+
+/// `elves` in the puzzle format, which parses back to the same elves.
+pub fn to_text(elves: &[Elf]) -> String {
+    let mut out = elves
+        .iter()
+        .map(|elf| {
+            elf.foods
+                .iter()
+                .map(|calories| format!("{calories}\n"))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // The blank line ending the input does not end an elf, only the next one does.
+    if elves.last().is_some_and(|elf| elf.foods.is_empty()) {
+        out.push('\n');
+    }
+    out
+}
+
+pub fn export(elves: &[Elf], format: Format) -> String {
+    match format {
+        Format::Csv => to_csv(elves),
+        Format::Json => to_json(elves),
+    }
+}
+
+fn to_csv(elves: &[Elf]) -> String {
+    let mut out = format!("{CSV_HEADER}\n");
+
+    for (idx, elf) in elves.iter().enumerate() {
+        for (item, calories) in elf.foods.iter().enumerate() {
+            writeln!(out, "{idx},{item},{calories}").expect("writing to a String cannot fail");
+        }
+        // An elf carrying nothing still gets a record.
+        if elf.foods.is_empty() {
+            writeln!(out, "{idx},,").expect("writing to a String cannot fail");
+        }
+    }
+    out
+}
+
+fn to_json(elves: &[Elf]) -> String {
+    if elves.is_empty() {
+        return "[]\n".to_owned();
+    }
+
+    let objects = elves
+        .iter()
+        .map(|elf| {
+            json::Object::new()
+                .numbers("foods", elf.foods.iter().map(|&calories| calories as u64))
+                .finish()
+        })
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+/// Elves of an inventory exported in `format`.
+pub fn import(s: &str, format: Format) -> anyhow::Result<Vec<Elf>> {
+    match format {
+        Format::Csv => Ok(from_csv(s)?),
+        Format::Json => from_json(s),
+    }
+}
+
+fn from_csv(s: &str) -> Result<Vec<Elf>, ParseError> {
+    let mut lines = s.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim_end() == CSV_HEADER => {}
+        Some((_, line)) => return Err(ParseError::new(line, 0, format!("`{CSV_HEADER}`"))),
+        None => return Err(ParseError::new("", 0, format!("`{CSV_HEADER}`"))),
+    }
+
+    let mut elves = Elves::default();
+    for (idx, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+        Scanner::whole(line, |s| {
+            let elf_offset = s.offset();
+            let elf = s.unsigned("an elf index")?;
+            s.literal(",")?;
+
+            let item_offset = s.offset();
+            let mut calories_offset = item_offset;
+            let item = if s.rest().starts_with(',') {
+                s.literal(",")?;
+                None
+            } else {
+                let item = s.unsigned("an item index or nothing")?;
+                s.literal(",")?;
+                calories_offset = s.offset();
+                Some((item, s.unsigned("a number of calories")?))
+            };
+
+            elves.push(elf, item).map_err(|(field, expected)| {
+                let offset = match field {
+                    Field::Elf => elf_offset,
+                    Field::Item => item_offset,
+                    Field::Calories => calories_offset,
+                };
+                ParseError::new(line, offset, expected)
+            })
+        })
+        .map_err(|e| e.on_line(idx + 1))?;
+    }
+    Ok(elves.0)
+}
+
+fn from_json(s: &str) -> anyhow::Result<Vec<Elf>> {
+    json::parse_objects(s)?
+        .iter()
+        .enumerate()
+        .map(|(idx, fields)| json_elf(fields).with_context(|| format!("invalid elf {idx}")))
+        .collect()
+}
+
+/// Elf of the fields of its JSON object.
+fn json_elf(fields: &[(String, Value)]) -> anyhow::Result<Elf> {
+    let mut foods = None;
+    for (key, value) in fields {
+        match (key.as_str(), value) {
+            ("foods", Value::Numbers(calories)) if foods.is_none() => foods = Some(calories),
+            ("foods", Value::Numbers(_)) => bail!("`foods` given twice"),
+            ("foods", _) => bail!("`foods` should be an array of numbers"),
+            _ => bail!("unknown field `{key}`"),
+        }
+    }
+
+    let mut elf = Elf::default();
+    for &calories in foods.context("missing `foods`")? {
+        let calories = usize::try_from(calories)?;
+        elf.total_calories = elf
+            .total_calories
+            .checked_add(calories)
+            .context("more calories than an elf can carry in total")?;
+        elf.foods.push(calories);
+    }
+    Ok(elf)
+}
+
+/// Field of a record an error is about.
+enum Field {
+    Elf,
+    Item,
+    Calories,
+}
+
+/// Elves read so far, records coming in the order they were exported in.
+#[derive(Default)]
+struct Elves(Vec<Elf>);
+
+impl Elves {
+    /// Add item `item` of elf `elf`, or the elf alone when it carries nothing.
+    fn push(&mut self, elf: usize, item: Option<(usize, usize)>) -> Result<(), (Field, String)> {
+        let next = self.0.len();
+        // An elf carrying nothing is alone on its record.
+        let open = item.is_some() && self.0.last().is_some_and(|last| !last.foods.is_empty());
+
+        if !(open && next.checked_sub(1) == Some(elf)) {
+            if elf != next {
+                let expected = if open {
+                    format!("elf index {} or {next}", next - 1)
+                } else {
+                    format!("elf index {next}")
+                };
+                return Err((Field::Elf, expected));
+            }
+            self.0.push(Elf::default());
+        }
+
+        let Some((item, calories)) = item else {
+            return Ok(());
+        };
+        let elf = &mut self.0[elf];
+        if item != elf.foods.len() {
+            return Err((Field::Item, format!("item index {}", elf.foods.len())));
+        }
+        elf.total_calories = elf.total_calories.checked_add(calories).ok_or((
+            Field::Calories,
+            "calories an elf can carry in total".to_owned(),
+        ))?;
+        elf.foods.push(calories);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+    use crate::{elves_from_string, Day1};
+
+    #[test]
+    fn round_trips() {
+        for text in [Day1::EXAMPLE, "\n1\n\n\n2\n\n\n", "", "\n", "3\n"] {
+            let elves = elves_from_string(text).unwrap();
+
+            assert_eq!(elves_from_string(&to_text(&elves)).unwrap(), elves);
+            for format in [Format::Csv, Format::Json] {
+                assert_eq!(import(&export(&elves, format), format).unwrap(), elves);
+            }
+        }
+
+        assert_eq!(to_text(&Day1::parse(Day1::EXAMPLE).unwrap()), Day1::EXAMPLE);
+    }
+
+    #[test]
+    fn csv_records() {
+        let elves = elves_from_string("1000\n2000\n\n\n3000").unwrap();
+
+        assert_eq!(
+            export(&elves, Format::Csv),
+            "elf_index,item_index,calories\n0,0,1000\n0,1,2000\n1,,\n2,0,3000\n"
+        );
+        assert_eq!(
+            export(&elves, Format::Json),
+            "[\n{\"foods\":[1000,2000]},\n{\"foods\":[]},\n{\"foods\":[3000]}\n]\n"
+        );
+        assert_eq!(export(&[], Format::Json), "[]\n");
+    }
+
+    #[test]
+    fn records_out_of_order() {
+        let err = from_csv("elf_index,item_index,calories\n0,0,1\n2,0,1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.expected, "elf index 0 or 1");
+
+        let err = from_csv("elf_index,item_index,calories\n0,1,1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = from_csv("elf_index,item_index,calories\n0,,\n0,0,1\n").unwrap_err();
+        assert_eq!(err.expected, "elf index 1");
+
+        let err = from_csv(&format!("{CSV_HEADER}\n0,0,1\n0,1,{}", usize::MAX)).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 5));
+
+        assert!(from_csv("elf,item,calories\n").is_err());
+        assert!(from_json("{\"foods\":[1]}\n").is_err());
+        assert!(from_json("[{\"elf\":0,\"foods\":[1]}]").is_err());
+        assert!(from_json("[{\"foods\":1}]").is_err());
+        assert!(from_json(&format!("[{{\"foods\":[1,{}]}}]", usize::MAX)).is_err());
+    }
+}
@@ -0,0 +1,340 @@
+pub mod generator;
+pub mod inventory;
+pub mod naive;
+pub mod plan;
+pub mod stats;
+
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
+
+use anyhow::Context;
+use common::{
+    input,
+    parse::{self, Mode, ParseError, Scanner},
+    Solution, Streaming,
+};
+
+#[derive(Default, Debug, Clone, Eq, PartialEq, PartialOrd)]
+pub struct Elf {
+    pub foods: Vec<usize>,
+    pub total_calories: usize,
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const EXAMPLE: &'static str = include_str!("../example.input.txt");
+
+    type Input = Vec<Elf>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse_with(input: &str, mode: Mode) -> anyhow::Result<Self::Input> {
+        Ok(parse_elves(input, mode)?)
+    }
+
+    fn part_one(elves: &Self::Input) -> anyhow::Result<usize> {
+        elves
+            .iter()
+            .map(|elf| elf.total_calories)
+            .max()
+            .context("No elf in input")
+    }
+
+    fn part_two(elves: &Self::Input) -> anyhow::Result<usize> {
+        let top = top_n(elves, 3);
+        anyhow::ensure!(top.elves.len() == 3, "Less than three elves in input");
+
+        Ok(top.total)
+    }
+}
+
+impl Streaming for Day1 {
+    /// Only keep the calories of the current elf and of the three best ones.
+    fn stream(reader: &mut dyn BufRead, mode: Mode) -> anyhow::Result<(usize, usize)> {
+        let top = stream_top_n(reader, 3, mode)?;
+
+        let (_, best) = top.elves.first().context("No elf in input")?;
+        anyhow::ensure!(top.elves.len() == 3, "Less than three elves in input");
+        Ok((*best, top.total))
+    }
+}
+
+/// [`parse_elves`] in strict mode.
+pub fn elves_from_string(buf: &str) -> Result<Vec<Elf>, ParseError> {
+    parse_elves(buf, Mode::Strict)
+}
+
+/// Elves of `buf`, malformed calories being skipped unless `mode` is strict.
+pub fn parse_elves(buf: &str, mode: Mode) -> Result<Vec<Elf>, ParseError> {
+    parse::blocks(buf)
+        .into_iter()
+        .map(|block| {
+            let mut elf = Elf::default();
+
+            for (idx, line) in block {
+                if let Some(calories) =
+                    mode.line(idx, line, |line| calories(line, elf.total_calories))?
+                {
+                    elf.total_calories += calories;
+                    elf.foods.push(calories);
+                }
+            }
+
+            Ok(elf)
+        })
+        .collect()
+}
+
+/// Calories of the food on `line`, as long as they fit along with the
+/// `carried` ones of its elf.
+fn calories(line: &str, carried: usize) -> Result<usize, ParseError> {
+    let calories = Scanner::whole(line, |s| s.unsigned("a number of calories"))?;
+    match carried.checked_add(calories) {
+        Some(_) => Ok(calories),
+        None => Err(ParseError::new(
+            line,
+            0,
+            "calories an elf can carry in total",
+        )),
+    }
+}
+
+/// Elves carrying the most calories, best first, along with their 0-based
+/// index in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Top<'a> {
+    pub elves: Vec<(usize, &'a Elf)>,
+    /// Calories carried by all of them, saturating instead of overflowing.
+    pub total: usize,
+}
+
+/// The `k` elves carrying the most calories, or all of them when there are
+/// fewer. Elves carrying as much are ranked in the order of the input.
+pub fn top_n(elves: &[Elf], k: usize) -> Top<'_> {
+    let mut ranked = elves.iter().enumerate().collect::<Vec<_>>();
+    let by_calories = |(a, elf_a): &(usize, &Elf), (b, elf_b): &(usize, &Elf)| {
+        elf_b
+            .total_calories
+            .cmp(&elf_a.total_calories)
+            .then(a.cmp(b))
+    };
+
+    // Only the k best matter, there is no need to sort the rest.
+    if k < ranked.len() {
+        ranked.select_nth_unstable_by(k, by_calories);
+        ranked.truncate(k);
+    }
+    ranked.sort_unstable_by(by_calories);
+
+    Top {
+        total: ranked.iter().fold(0, |total, (_, elf)| {
+            total.saturating_add(elf.total_calories)
+        }),
+        elves: ranked,
+    }
+}
+
+/// Calories carried by the elves carrying the most, best first, along with
+/// their 0-based index in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopTotals {
+    pub elves: Vec<(usize, usize)>,
+    /// Calories carried by all of them, saturating instead of overflowing.
+    pub total: usize,
+}
+
+/// Running [`top_n`] over lines of input read one at a time, only keeping the
+/// total of the elf being read and of the `k` best ones so far.
+#[derive(Debug, Clone)]
+pub struct TopK {
+    k: usize,
+    /// Worst of the kept elves on top: the one with the fewest calories, the
+    /// latest in the input among those carrying as much.
+    kept: BinaryHeap<Reverse<(usize, Reverse<usize>)>>,
+    elves: usize,
+    current: Option<usize>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        TopK {
+            k,
+            kept: BinaryHeap::new(),
+            elves: 0,
+            current: None,
+        }
+    }
+
+    /// Read line `idx` of the input, a blank one ending the current elf and
+    /// a malformed one being skipped unless `mode` is strict.
+    pub fn line(&mut self, idx: usize, line: &str, mode: Mode) -> Result<(), ParseError> {
+        if line.trim().is_empty() {
+            let calories = self.current.take().unwrap_or(0);
+            self.end_of_elf(calories);
+            return Ok(());
+        }
+
+        let carried = self.current.unwrap_or(0);
+        if let Some(calories) = mode.line(idx, line, |line| calories(line, carried))? {
+            self.current = Some(carried + calories);
+        }
+        Ok(())
+    }
+
+    fn end_of_elf(&mut self, calories: usize) {
+        let elf = (calories, Reverse(self.elves));
+        self.elves += 1;
+
+        if self.kept.len() < self.k {
+            self.kept.push(Reverse(elf));
+        } else if let Some(mut worst) = self.kept.peek_mut() {
+            // Later elves lose ties, so only strictly more calories get in.
+            if elf > worst.0 {
+                *worst = Reverse(elf);
+            }
+        }
+    }
+
+    /// Best elves of the whole input, once every line has been read.
+    pub fn finish(mut self) -> TopTotals {
+        // The last elf is not followed by an empty line.
+        if let Some(calories) = self.current.take() {
+            self.end_of_elf(calories);
+        }
+
+        // Ascending order of `Reverse` is the best elf first.
+        let elves = self
+            .kept
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(idx)))| (idx, calories))
+            .collect::<Vec<_>>();
+        TopTotals {
+            total: elves
+                .iter()
+                .fold(0, |total, (_, calories)| total.saturating_add(*calories)),
+            elves,
+        }
+    }
+}
+
+/// [`top_n`] of the elves read from `reader`, in memory bounded by `k`
+/// whatever the size of the input.
+pub fn stream_top_n(reader: &mut dyn BufRead, k: usize, mode: Mode) -> anyhow::Result<TopTotals> {
+    let mut top = TopK::new(k);
+    input::for_each_line(reader, |idx, line| Ok(top.line(idx, line, mode)?))?;
+    Ok(top.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_elf_without_trailing_empty_line() {
+        let elves = elves_from_string("1000\n2000\n\n3000").unwrap();
+
+        assert_eq!(
+            elves,
+            vec![
+                Elf {
+                    foods: vec![1000, 2000],
+                    total_calories: 3000,
+                },
+                Elf {
+                    foods: vec![3000],
+                    total_calories: 3000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn too_many_calories() {
+        let err = elves_from_string(&format!("1\n{}\n", usize::MAX)).unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn invalid_calories() {
+        let err = elves_from_string("1000\n20x0\n").unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn strict_mode() {
+        let input = "1000\nx\n\n2000\n\n3000\n";
+
+        assert_eq!(Day1::part_two(&Day1::parse(input).unwrap()).unwrap(), 6000);
+        let err = Day1::parse_with(input, Mode::Strict).unwrap_err();
+        assert_eq!(err.downcast_ref::<ParseError>().unwrap().line, 2);
+
+        assert_eq!(
+            Day1::stream(&mut input.as_bytes(), Mode::Lenient).unwrap(),
+            (3000, 6000)
+        );
+        assert!(Day1::stream(&mut input.as_bytes(), Mode::Strict).is_err());
+    }
+
+    #[test]
+    fn top_elves() {
+        let elves = Day1::parse(Day1::EXAMPLE).unwrap();
+        let top = top_n(&elves, 3);
+
+        assert_eq!(
+            top.elves
+                .iter()
+                .map(|(idx, elf)| (*idx, elf.total_calories))
+                .collect::<Vec<_>>(),
+            vec![(3, 24000), (2, 11000), (4, 10000)]
+        );
+        assert_eq!(top.total, 45000);
+        assert_eq!(top_n(&elves, 0).total, 0);
+        assert_eq!(top_n(&elves, 10).elves.len(), elves.len());
+    }
+
+    #[test]
+    fn ties_keep_input_order() {
+        let elves = elves_from_string("5\n\n7\n\n5\n\n5").unwrap();
+        let top = top_n(&elves, 3);
+
+        assert_eq!(
+            top.elves.iter().map(|(idx, _)| *idx).collect::<Vec<_>>(),
+            vec![1, 0, 2]
+        );
+    }
+
+    #[test]
+    fn fewer_than_three_elves() {
+        let elves = elves_from_string("1000\n\n2000").unwrap();
+
+        assert_eq!(Day1::part_one(&elves).unwrap(), 2000);
+        assert!(Day1::part_two(&elves).is_err());
+        assert_eq!(top_n(&elves, 3).total, 3000);
+    }
+
+    #[test]
+    fn streamed_top_elves() {
+        let top = stream_top_n(&mut Day1::EXAMPLE.as_bytes(), 3, Mode::Strict).unwrap();
+
+        assert_eq!(top.elves, vec![(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(top.total, 45000);
+
+        let ties = stream_top_n(&mut "5\n\n7\n\n5\n\n5".as_bytes(), 3, Mode::Strict).unwrap();
+        assert_eq!(ties.elves, vec![(1, 7), (0, 5), (2, 5)]);
+        assert!(stream_top_n(&mut "1\n\n2".as_bytes(), 0, Mode::Strict)
+            .unwrap()
+            .elves
+            .is_empty());
+    }
+
+    #[test]
+    fn streamed_empty_elves() {
+        let top = stream_top_n(&mut "\n\n3\n\n".as_bytes(), 5, Mode::Strict).unwrap();
+
+        assert_eq!(top.elves, vec![(2, 3), (0, 0), (1, 0)]);
+    }
+}
@@ -1,54 +1,120 @@
 use anyhow::Context;
-use std::io::Read;
+use common::{input::Source, parse::Mode, Solution};
+use day_1::{
+    inventory::{self, Format},
+    plan::{self, Goal},
+    stats, stream_top_n, Day1,
+};
 
-#[derive(Default, Debug, Clone, Eq, PartialEq, PartialOrd)]
-struct Elf {
-    foods: Vec<usize>,
-    total_calories: usize,
-}
+const USAGE: &str = "\
+usage: day_1 [--top <K>] [INPUT]
+       day_1 stats [--buckets <N>] [INPUT]
+       day_1 plan [--tolerance <CALORIES>] [INPUT]
+       day_1 export <csv|json> [INPUT]
+       day_1 import <csv|json> <INVENTORY>
 
-fn main() -> anyhow::Result<()> {
-    let mut args = std::env::args();
+INPUT defaults to input.txt, INVENTORY is a file path or `-` to read stdin.";
 
-    let input_file = args.nth(1).context("Missing input file argument")?;
+/// Buckets of the calories histogram when none are asked for.
+const DEFAULT_BUCKETS: usize = 10;
 
-    let mut file = std::fs::File::open(input_file).context("Failed to open input file")?;
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
 
-    let mut buf = String::new();
-    file.read_to_string(&mut buf)
-        .context("Failed to read input file")?;
+    let command = match args.first().map(String::as_str) {
+        Some("stats" | "plan" | "export" | "import") => Some(args.remove(0)),
+        _ => None,
+    };
+    let format = match command.as_deref() {
+        Some("export" | "import") => {
+            anyhow::ensure!(!args.is_empty(), USAGE);
+            Some(args.remove(0).parse::<Format>()?)
+        }
+        _ => None,
+    };
+    let top = take_number(&mut args, "--top")?;
+    let buckets = take_number(&mut args, "--buckets")?;
+    let tolerance = take_number(&mut args, "--tolerance")?;
+    anyhow::ensure!(top.is_none() || command.is_none(), USAGE);
+    anyhow::ensure!(
+        buckets.is_none() || command.as_deref() == Some("stats"),
+        USAGE
+    );
+    anyhow::ensure!(
+        tolerance.is_none() || command.as_deref() == Some("plan"),
+        USAGE
+    );
 
-    let mut elves = elves_from_string(&buf)?;
-    elves.sort_by(|a, b| a.total_calories.cmp(&b.total_calories));
-    elves.reverse();
+    // Unlike the puzzle input, an inventory has neither a default nor an example.
+    if command.as_deref() == Some("import") {
+        anyhow::ensure!(args.len() == 1 && args[0] != "--example", USAGE);
+    }
+    let source = Source::from_args(args)?;
 
-    println!("Top elf calories: {}", elves[0].total_calories);
+    match command.as_deref() {
+        Some("stats") => {
+            let elves = Day1::parse(&source.read(Day1::EXAMPLE)?)?;
+            print!(
+                "{}",
+                stats::stats(&elves, buckets.unwrap_or(DEFAULT_BUCKETS))?
+            );
+            return Ok(());
+        }
+        Some("plan") => {
+            let elves = Day1::parse(&source.read(Day1::EXAMPLE)?)?;
+            let goal = tolerance.map_or(Goal::Balance, Goal::Within);
+            let plan = plan::plan(&elves, goal)?
+                .context("No plan gets every elf within the tolerance of the mean")?;
+            print!("{plan}");
+            return Ok(());
+        }
+        Some("export") => {
+            let elves = Day1::parse(&source.read(Day1::EXAMPLE)?)?;
+            print!("{}", inventory::export(&elves, format.context(USAGE)?));
+            return Ok(());
+        }
+        Some("import") => {
+            let elves = inventory::import(&source.read("")?, format.context(USAGE)?)?;
+            print!("{}", inventory::to_text(&elves));
+            return Ok(());
+        }
+        _ => {}
+    }
 
-    let total = elves[0..3]
-        .iter()
-        .map(|elf| elf.total_calories)
-        .sum::<usize>();
+    let Some(k) = top else {
+        let elves = Day1::parse(&source.read(Day1::EXAMPLE)?)?;
+        println!("Top elf calories: {}", Day1::part_one(&elves)?);
+        println!(
+            "Total calories for the 3 top elves: {}",
+            Day1::part_two(&elves)?
+        );
+        return Ok(());
+    };
 
-    println!("Total calories for the 3 top elves: {total:?}");
+    // Inputs may not fit in memory, only the best elves are kept.
+    let top = stream_top_n(&mut source.open(Day1::EXAMPLE)?, k, Mode::default())?;
+    for (rank, (idx, calories)) in top.elves.iter().enumerate() {
+        println!("#{}: elf {} with {calories} calories", rank + 1, idx + 1);
+    }
+    println!(
+        "Total calories for the {} top elves: {}",
+        top.elves.len(),
+        top.total
+    );
 
     Ok(())
 }
 
-fn elves_from_string(buf: &str) -> anyhow::Result<Vec<Elf>> {
-    let mut elves: Vec<Elf> = vec![];
-
-    let mut elf = Elf::default();
-    for line in buf.lines() {
-        if line == "" {
-            elf.total_calories = elf.foods.iter().sum::<usize>();
-            elves.push(Elf::default());
-            elf = std::mem::replace(elves.last_mut().unwrap(), elf);
-            continue;
-        }
-
-        let calories = line.parse().context("invalid food calories")?;
-        elf.foods.push(calories);
-    }
+/// Value of `--flag N` in `args`, removing both.
+fn take_number(args: &mut Vec<String>, flag: &str) -> anyhow::Result<Option<usize>> {
+    let Some(idx) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
 
-    Ok(elves)
+    let value = args.get(idx + 1).context(USAGE)?;
+    let n = value
+        .parse()
+        .with_context(|| format!("invalid value `{value}` for {flag}"))?;
+    args.drain(idx..=idx + 1);
+    Ok(Some(n))
 }
@@ -0,0 +1,26 @@
+//! Straightforward implementations, kept as reference for the optimized ones.
+
+use anyhow::Context;
+
+use crate::Elf;
+
+pub fn part_one(elves: &[Elf]) -> anyhow::Result<usize> {
+    let mut calories = elves
+        .iter()
+        .map(|elf| elf.foods.iter().sum::<usize>())
+        .collect::<Vec<usize>>();
+    calories.sort_unstable();
+
+    calories.last().copied().context("No elf in input")
+}
+
+pub fn part_two(elves: &[Elf]) -> anyhow::Result<usize> {
+    let mut calories = elves
+        .iter()
+        .map(|elf| elf.foods.iter().sum::<usize>())
+        .collect::<Vec<usize>>();
+    calories.sort_unstable_by(|a, b| b.cmp(a));
+
+    anyhow::ensure!(calories.len() >= 3, "Less than three elves in input");
+    Ok(calories[0..3].iter().sum())
+}
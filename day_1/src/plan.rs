@@ -0,0 +1,597 @@
+//! Moving food items between elves so that they carry about as much.
+//!
+//! Sharing the items out is multiway number partitioning: an [`exact`] search
+//! for small inventories, and the longest processing time first heuristic,
+//! [`lpt`], for the others. The heaviest elf of the latter carries at most
+//! `4/3 - 1/(3m)` times as much as in the best plan for `m` elves.
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::{self, Display},
+};
+
+use anyhow::Context;
+
+use crate::Elf;
+
+/// Items up to which [`plan`] searches for the best plan, the search being
+/// exponential in the number of items.
+pub const EXACT_ITEMS: usize = 24;
+
+/// Partial plans the [`exact`] search goes through at most, before settling
+/// for the best plan found so far. The search space grows as the number of
+/// elves to the power of the number of items, so that a few items shared out
+/// between many elves is already too much to go through.
+pub const EXACT_NODES: usize = 200_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// The heaviest elf carries as little as possible.
+    Balance,
+    /// Every elf carries within this many calories of the mean.
+    Within(usize),
+}
+
+/// Item `item` of elf `from` given to elf `to`, indices being 0-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: usize,
+    pub item: usize,
+    pub to: usize,
+    pub calories: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub transfers: Vec<Transfer>,
+    /// Calories carried by each elf once the items are moved.
+    pub totals: Vec<usize>,
+    /// Calories the heaviest elf carries in any plan, at least.
+    pub lower_bound: usize,
+    /// Whether the plan is known to be the best one, or to meet the tolerance
+    /// asked for, rather than guessed.
+    pub exact: bool,
+    /// Partial plans the [`exact`] search went through, at most
+    /// [`EXACT_NODES`].
+    pub nodes: usize,
+}
+
+impl Plan {
+    /// Calories carried by the heaviest elf.
+    pub fn max(&self) -> usize {
+        self.totals.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Whether every elf carries within `tolerance` calories of the mean.
+    pub fn within(&self, tolerance: usize) -> bool {
+        let mean = mean(&self.totals);
+        self.totals
+            .iter()
+            .all(|&total| (total as f64 - mean).abs() <= tolerance as f64)
+    }
+
+    /// `elves` once the items are moved, each one appending the items it is
+    /// given to those it keeps.
+    pub fn apply(&self, elves: &[Elf]) -> Vec<Elf> {
+        let mut kept = elves
+            .iter()
+            .map(|elf| vec![true; elf.foods.len()])
+            .collect::<Vec<_>>();
+        for transfer in &self.transfers {
+            kept[transfer.from][transfer.item] = false;
+        }
+
+        let mut moved = elves
+            .iter()
+            .zip(&kept)
+            .map(|(elf, kept)| {
+                let foods = elf
+                    .foods
+                    .iter()
+                    .zip(kept)
+                    .filter(|(_, kept)| **kept)
+                    .map(|(calories, _)| *calories)
+                    .collect();
+                Elf {
+                    foods,
+                    total_calories: 0,
+                }
+            })
+            .collect::<Vec<_>>();
+        for transfer in &self.transfers {
+            moved[transfer.to].foods.push(transfer.calories);
+        }
+        for elf in &mut moved {
+            elf.total_calories = elf.foods.iter().sum();
+        }
+        moved
+    }
+
+    /// Plan giving each of `items` to the elf at the same index in `bins`.
+    ///
+    /// Which elf ends up with which share does not matter to the totals, so
+    /// shares go to the elves already owning the most of their items first.
+    fn new(elves: &[Elf], items: &[Item], bins: &[usize], exact: bool) -> Self {
+        let m = elves.len();
+
+        // Only the shares and elves some item links are worth matching, as
+        // many as there are items at most.
+        let mut owned = items
+            .iter()
+            .zip(bins)
+            .map(|(item, &bin)| (bin, item.elf))
+            .collect::<Vec<_>>();
+        owned.sort_unstable();
+        let mut pairs = owned
+            .chunk_by(|a, b| a == b)
+            .map(|same| (same.len(), same[0]))
+            .collect::<Vec<_>>();
+        pairs.sort_by_key(|&(count, pair)| (Reverse(count), pair));
+
+        let mut receiver = vec![None; m];
+        let mut taken = vec![false; m];
+        for (_, (bin, elf)) in pairs {
+            if receiver[bin].is_none() && !taken[elf] {
+                receiver[bin] = Some(elf);
+                taken[elf] = true;
+            }
+        }
+        // Shares left over go to the elves left over, in order.
+        let mut left = (0..m).filter(|&elf| !taken[elf]);
+        let receiver = receiver
+            .into_iter()
+            .map(|elf| {
+                elf.or_else(|| left.next())
+                    .expect("as many shares as elves")
+            })
+            .collect::<Vec<_>>();
+
+        let mut totals = vec![0; m];
+        let mut transfers = Vec::new();
+        for (item, &bin) in items.iter().zip(bins) {
+            let to = receiver[bin];
+            totals[to] += item.calories;
+            if to != item.elf {
+                transfers.push(Transfer {
+                    from: item.elf,
+                    item: item.item,
+                    to,
+                    calories: item.calories,
+                });
+            }
+        }
+        transfers.sort_by_key(|transfer| (transfer.from, transfer.item));
+
+        let total = totals.iter().sum::<usize>();
+        Plan {
+            transfers,
+            totals,
+            lower_bound: total
+                .div_ceil(m.max(1))
+                .max(items.first().map_or(0, |item| item.calories)),
+            exact,
+            nodes: 0,
+        }
+    }
+}
+
+/// Plan meeting `goal`, searched for when `elves` carry up to [`EXACT_ITEMS`]
+/// items. None when no plan gets every elf within the tolerance asked for, or
+/// none was found.
+pub fn plan(elves: &[Elf], goal: Goal) -> anyhow::Result<Option<Plan>> {
+    let items = elves.iter().map(|elf| elf.foods.len()).sum::<usize>();
+    if items <= EXACT_ITEMS {
+        return exact(elves, goal);
+    }
+
+    let plan = lpt(elves)?;
+    Ok(match goal {
+        Goal::Balance => Some(plan),
+        Goal::Within(tolerance) => plan.within(tolerance).then_some(plan),
+    })
+}
+
+/// Each item in turn, the most calories first, given to the elf carrying the
+/// least so far.
+pub fn lpt(elves: &[Elf]) -> anyhow::Result<Plan> {
+    let items = items(elves)?;
+
+    let mut loads = (0..elves.len())
+        .map(|elf| Reverse((0, elf)))
+        .collect::<BinaryHeap<_>>();
+    let mut bins = vec![0; items.len()];
+    for (item, bin) in items.iter().zip(&mut bins) {
+        let Some(mut lightest) = loads.peek_mut() else {
+            break;
+        };
+        let Reverse((load, elf)) = *lightest;
+        *bin = elf;
+        *lightest = Reverse((load + item.calories, elf));
+    }
+
+    Ok(Plan::new(elves, &items, &bins, false))
+}
+
+/// Best plan for `goal`, going through up to [`EXACT_NODES`] partial plans and
+/// falling back to the [`lpt`] one when that is not enough. None when no plan
+/// gets every elf within the tolerance asked for, or none was found.
+pub fn exact(elves: &[Elf], goal: Goal) -> anyhow::Result<Option<Plan>> {
+    let items = items(elves)?;
+    let greedy = lpt(elves)?;
+    if goal == Goal::Balance && greedy.max() <= greedy.lower_bound {
+        return Ok(Some(Plan {
+            exact: true,
+            ..greedy
+        }));
+    }
+
+    let (lower, limit) = match goal {
+        // Only plans lighter than the greedy one are worth searching for.
+        Goal::Balance => (0, greedy.max()),
+        Goal::Within(tolerance) => {
+            let mean = mean(&greedy.totals);
+            let lower = (mean - tolerance as f64).ceil().max(0.) as usize;
+            let upper = (mean + tolerance as f64).floor() as usize;
+            (lower, upper.saturating_add(1))
+        }
+    };
+
+    let mut search = Search {
+        calories: items.iter().map(|item| item.calories).collect(),
+        remaining: Vec::new(),
+        loads: vec![0; elves.len()],
+        bins: vec![0; items.len()],
+        lower,
+        limit,
+        stop_at: match goal {
+            Goal::Balance => greedy.lower_bound,
+            Goal::Within(_) => usize::MAX,
+        },
+        nodes: EXACT_NODES,
+        best: None,
+    };
+    search.remaining = search
+        .calories
+        .iter()
+        .rev()
+        .scan(0, |sum, calories| {
+            *sum += calories;
+            Some(*sum)
+        })
+        .collect();
+    search.remaining.reverse();
+    search.remaining.push(0);
+
+    search.assign(0);
+    let exhausted = search.nodes == 0;
+    let nodes = EXACT_NODES - search.nodes;
+    Ok(match (search.best, goal) {
+        (Some(bins), _) => Some(Plan {
+            nodes,
+            ..Plan::new(elves, &items, &bins, !exhausted)
+        }),
+        (None, Goal::Balance) => Some(Plan {
+            exact: !exhausted,
+            nodes,
+            ..greedy
+        }),
+        (None, Goal::Within(tolerance)) => {
+            (exhausted && greedy.within(tolerance)).then_some(Plan { nodes, ..greedy })
+        }
+    })
+}
+
+/// Food item `item` of elf `elf`.
+#[derive(Debug, Clone, Copy)]
+struct Item {
+    elf: usize,
+    item: usize,
+    calories: usize,
+}
+
+/// Items of all elves, the most calories first.
+///
+/// Fails when they carry more calories altogether than a `usize` holds, so
+/// that neither the calories an elf ends up with nor any sum of items can
+/// overflow once shared out.
+fn items(elves: &[Elf]) -> anyhow::Result<Vec<Item>> {
+    elves
+        .iter()
+        .try_fold(0usize, |total, elf| total.checked_add(elf.total_calories))
+        .context("Too many calories altogether to share them out")?;
+
+    let mut items = elves
+        .iter()
+        .enumerate()
+        .flat_map(|(elf, Elf { foods, .. })| {
+            foods.iter().enumerate().map(move |(item, &calories)| Item {
+                elf,
+                item,
+                calories,
+            })
+        })
+        .collect::<Vec<_>>();
+    items.sort_by_key(|item| Reverse(item.calories));
+    Ok(items)
+}
+
+fn mean(totals: &[usize]) -> f64 {
+    totals.iter().map(|&total| total as f64).sum::<f64>() / totals.len().max(1) as f64
+}
+
+/// Branch and bound over the elf given each item, in the order of `calories`.
+struct Search {
+    calories: Vec<usize>,
+    /// Calories of the items from each one on.
+    remaining: Vec<usize>,
+    loads: Vec<usize>,
+    bins: Vec<usize>,
+    /// Calories every elf must carry, at least.
+    lower: usize,
+    /// Calories no elf may reach.
+    limit: usize,
+    /// Heaviest elf below which no plan can go, ending the search.
+    stop_at: usize,
+    /// Partial plans left to go through.
+    nodes: usize,
+    best: Option<Vec<usize>>,
+}
+
+impl Search {
+    /// Whether the search is over.
+    fn assign(&mut self, idx: usize) -> bool {
+        let Some(nodes) = self.nodes.checked_sub(1) else {
+            return true;
+        };
+        self.nodes = nodes;
+
+        let missing = self
+            .loads
+            .iter()
+            .map(|load| self.lower.saturating_sub(*load))
+            .sum::<usize>();
+        if missing > self.remaining[idx] {
+            return false;
+        }
+
+        let Some(&calories) = self.calories.get(idx) else {
+            let max = self.loads.iter().copied().max().unwrap_or(0);
+            self.best = Some(self.bins.clone());
+            self.limit = max;
+            return max <= self.stop_at;
+        };
+
+        for elf in 0..self.loads.len() {
+            let load = self.loads[elf];
+            // Elves carrying as much are interchangeable.
+            if self.loads[..elf].contains(&load) || load + calories >= self.limit {
+                continue;
+            }
+
+            self.loads[elf] += calories;
+            self.bins[idx] = elf;
+            let done = self.assign(idx + 1);
+            self.loads[elf] -= calories;
+            if done {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for transfer in &self.transfers {
+            writeln!(
+                f,
+                "elf {} gives item {} ({} calories) to elf {}",
+                transfer.from + 1,
+                transfer.item + 1,
+                transfer.calories,
+                transfer.to + 1
+            )?;
+        }
+        writeln!(
+            f,
+            "{} items moved, the heaviest elf carrying {} calories ({}, no less than {})",
+            self.transfers.len(),
+            self.max(),
+            if self.exact {
+                "best plan"
+            } else {
+                "greedy plan"
+            },
+            self.lower_bound
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{generator::Rng, Solution};
+
+    use super::*;
+    use crate::{elves_from_string, Day1};
+
+    #[test]
+    fn balance_the_example() {
+        let elves = Day1::parse(Day1::EXAMPLE).unwrap();
+        let plan = plan(&elves, Goal::Balance).unwrap().unwrap();
+
+        assert!(plan.exact);
+        assert_eq!((plan.max(), plan.lower_bound), (11000, 11000));
+        assert_eq!(plan.totals.iter().sum::<usize>(), 55000);
+
+        let moved = plan.apply(&elves);
+        assert_eq!(
+            moved
+                .iter()
+                .map(|elf| elf.total_calories)
+                .collect::<Vec<_>>(),
+            plan.totals
+        );
+        let items = |elves: &[Elf]| elves.iter().map(|elf| elf.foods.len()).sum::<usize>();
+        assert_eq!(items(&moved), items(&elves));
+        assert!(plan
+            .transfers
+            .iter()
+            .all(|t| elves[t.from].foods[t.item] == t.calories && t.from != t.to));
+    }
+
+    #[test]
+    fn greedy_is_not_always_best() {
+        // LPT shares them out as 7+3+2 and 6+4, the best plan being 7+4 and 6+3+2.
+        let mut elves = elves_from_string("7\n6\n4\n3\n2").unwrap();
+        elves.push(Elf::default());
+
+        assert_eq!(lpt(&elves).unwrap().max(), 12);
+        assert_eq!(exact(&elves, Goal::Balance).unwrap().unwrap().max(), 11);
+    }
+
+    #[test]
+    fn many_elves_stop_searching() {
+        let mut rng = Rng::new(24);
+        let elves = (0..8)
+            .map(|_| {
+                let foods = (0..rng.range(2..=3))
+                    .map(|_| rng.range(1_000..=70_000))
+                    .collect::<Vec<_>>();
+                Elf {
+                    total_calories: foods.iter().sum(),
+                    foods,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let plan = plan(&elves, Goal::Balance).unwrap().unwrap();
+        assert_eq!(plan.nodes, EXACT_NODES);
+        assert!(!plan.exact);
+        assert!(plan.lower_bound <= plan.max() && plan.max() <= lpt(&elves).unwrap().max());
+    }
+
+    #[test]
+    fn within_tolerance() {
+        let elves = elves_from_string("4\n4\n\n1\n\n3").unwrap();
+
+        let shared = plan(&elves, Goal::Within(0)).unwrap().unwrap();
+        assert_eq!(shared.totals, vec![4, 4, 4]);
+        assert_eq!(shared.transfers.len(), 2);
+
+        let elves = elves_from_string("5\n\n1\n\n1").unwrap();
+        assert!(plan(&elves, Goal::Within(1)).unwrap().is_none());
+        assert!(plan(&elves, Goal::Within(3)).unwrap().is_some());
+    }
+
+    #[test]
+    fn nothing_to_move() {
+        let elves = elves_from_string("3\n\n3\n\n").unwrap();
+        let plan = plan(&elves, Goal::Balance).unwrap().unwrap();
+
+        assert!(plan.transfers.is_empty());
+        assert!(plan.within(0));
+        assert_eq!(lpt(&[]).unwrap().max(), 0);
+    }
+
+    #[test]
+    fn too_many_calories() {
+        let elf = Elf {
+            foods: vec![usize::MAX / 2 + 1],
+            total_calories: usize::MAX / 2 + 1,
+        };
+        let elves = [elf.clone(), elf];
+
+        assert!(lpt(&elves).is_err());
+        assert!(plan(&elves, Goal::Balance).is_err());
+        assert!(plan(&elves, Goal::Within(0)).is_err());
+    }
+
+    /// `count` elves carrying `items` items between them, of up to `max` calories.
+    fn inventory(rng: &mut Rng, count: usize, items: usize, max: usize) -> Vec<Elf> {
+        let mut elves = vec![Elf::default(); count];
+        for _ in 0..items {
+            let elf = &mut elves[rng.range(0..=count - 1)];
+            let calories = rng.range(1..=max);
+            elf.foods.push(calories);
+            elf.total_calories += calories;
+        }
+        elves
+    }
+
+    fn assert_consistent(elves: &[Elf], plan: &Plan) {
+        let moved = plan.apply(elves);
+        assert_eq!(
+            moved
+                .iter()
+                .map(|elf| elf.total_calories)
+                .collect::<Vec<_>>(),
+            plan.totals
+        );
+        assert!(plan.lower_bound <= plan.max());
+        assert!(plan.nodes <= EXACT_NODES);
+    }
+
+    #[test]
+    fn greedy_plans_stay_within_their_bound() {
+        let mut rng = Rng::new(0x13);
+
+        for _ in 0..100 {
+            let (count, items) = (rng.range(1..=4), rng.range(0..=16));
+            let elves = inventory(&mut rng, count, items, 20);
+
+            let greedy = lpt(&elves).unwrap();
+            let best = exact(&elves, Goal::Balance).unwrap().unwrap();
+            assert!(best.exact);
+            assert!(best.max() <= greedy.max());
+            assert!(greedy.max() * 3 * count <= best.max() * (4 * count - 1));
+
+            assert_consistent(&elves, &greedy);
+            assert_consistent(&elves, &best);
+        }
+    }
+
+    #[test]
+    fn plans_around_the_exact_threshold() {
+        let mut rng = Rng::new(0x14);
+
+        for items in EXACT_ITEMS - 1..=EXACT_ITEMS + 1 {
+            for count in [2, 3, 8, 30] {
+                let elves = inventory(&mut rng, count, items, 70_000);
+
+                let plan = plan(&elves, Goal::Balance).unwrap().unwrap();
+                assert_consistent(&elves, &plan);
+                assert!(plan.max() <= lpt(&elves).unwrap().max());
+                if items > EXACT_ITEMS {
+                    assert_eq!(plan.nodes, 0);
+                    assert!(!plan.exact);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn plans_for_many_elves() {
+        let mut rng = Rng::new(0x15);
+        let elves = inventory(&mut rng, 20_000, 60_000, 70_000);
+
+        let plan = plan(&elves, Goal::Balance).unwrap().unwrap();
+        assert_consistent(&elves, &plan);
+        assert!(!plan.exact);
+        assert_eq!(plan.totals.len(), elves.len());
+        // The heaviest elf was the lightest one before getting its last item.
+        assert!(plan.max() <= plan.lower_bound + 70_000);
+
+        // Each elf on its own fits in a usize, not all of them.
+        let heavy = elves
+            .iter()
+            .take(3)
+            .map(|elf| Elf {
+                foods: vec![usize::MAX / 2, elf.total_calories + 1],
+                total_calories: usize::MAX / 2 + elf.total_calories + 1,
+            })
+            .collect::<Vec<_>>();
+        assert!(super::plan(&heavy, Goal::Balance).is_err());
+        assert!(super::plan(&heavy, Goal::Within(10)).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Descriptive statistics over the calories carried by the elves.
+
+use std::fmt::{self, Display};
+
+use anyhow::Context;
+
+use crate::Elf;
+
+/// Percentiles of the calories reported along with the median.
+pub const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// Longest bar of a histogram, in characters.
+const BAR_WIDTH: usize = 40;
+
+/// How a quantity is spread over the elves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Standard deviation of the whole population of elves.
+    pub std_dev: f64,
+}
+
+/// Elves whose calories lie between `start` and `end`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: usize,
+    pub end: usize,
+    pub elves: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub elves: usize,
+    /// Calories carried by all elves, saturating instead of overflowing.
+    pub total_calories: usize,
+    pub calories: Summary,
+    /// Each of [`PERCENTILES`] along with the calories it falls at.
+    pub percentiles: Vec<(u8, f64)>,
+    /// Items carried by each elf, in the order of the input.
+    pub items: Vec<usize>,
+    pub items_per_elf: Summary,
+    /// Buckets of the same width covering the calories from the least to the
+    /// most carried.
+    pub histogram: Vec<Bucket>,
+}
+
+/// Statistics of `elves`, their calories spread over up to `buckets` buckets.
+pub fn stats(elves: &[Elf], buckets: usize) -> anyhow::Result<Stats> {
+    anyhow::ensure!(buckets > 0, "A histogram needs at least one bucket");
+
+    let mut calories = elves
+        .iter()
+        .map(|elf| elf.total_calories)
+        .collect::<Vec<_>>();
+    calories.sort_unstable();
+    let items = elves.iter().map(|elf| elf.foods.len()).collect::<Vec<_>>();
+    let mut sorted_items = items.clone();
+    sorted_items.sort_unstable();
+
+    Ok(Stats {
+        elves: elves.len(),
+        total_calories: calories.iter().fold(0, |total, c| total.saturating_add(*c)),
+        calories: summary(&calories).context("No elf in input")?,
+        percentiles: PERCENTILES
+            .iter()
+            .map(|&p| (p, percentile(&calories, f64::from(p) / 100.)))
+            .collect(),
+        items_per_elf: summary(&sorted_items).context("No elf in input")?,
+        items,
+        histogram: histogram(&calories, buckets),
+    })
+}
+
+/// Summary of `sorted`, none when it is empty.
+fn summary(sorted: &[usize]) -> Option<Summary> {
+    let (&min, &max) = (sorted.first()?, sorted.last()?);
+
+    let n = sorted.len() as f64;
+    let mean = sorted.iter().map(|&x| x as f64).sum::<f64>() / n;
+    let variance = sorted
+        .iter()
+        .map(|&x| (x as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+
+    Some(Summary {
+        min,
+        max,
+        mean,
+        median: percentile(sorted, 0.5),
+        std_dev: variance.sqrt(),
+    })
+}
+
+/// Value below which a fraction `p` of `sorted` falls, interpolating between
+/// the closest ranks.
+fn percentile(sorted: &[usize], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+    let (low, high) = (sorted[below] as f64, sorted[above] as f64);
+
+    low + (high - low) * (rank - below as f64)
+}
+
+fn histogram(sorted: &[usize], buckets: usize) -> Vec<Bucket> {
+    let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+        return Vec::new();
+    };
+
+    // Round widths may leave the last buckets past the most calories carried.
+    let width = (max - min).div_ceil(buckets).max(1);
+    let buckets = buckets.min((max - min) / width + 1);
+
+    let mut histogram = (0..buckets)
+        .map(|idx| Bucket {
+            start: min + idx * width,
+            end: if idx + 1 == buckets {
+                max
+            } else {
+                min + (idx + 1) * width - 1
+            },
+            elves: 0,
+        })
+        .collect::<Vec<_>>();
+    for &calories in sorted {
+        histogram[((calories - min) / width).min(buckets - 1)].elves += 1;
+    }
+    histogram
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {}, max {}, mean {:.1}, median {:.1}, std dev {:.1}",
+            self.min, self.max, self.mean, self.median, self.std_dev
+        )
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} elves carrying {} items, {} calories in total",
+            self.elves,
+            self.items.iter().sum::<usize>(),
+            self.total_calories
+        )?;
+        writeln!(f, "Calories: {}", self.calories)?;
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(p, calories)| format!("p{p} {calories:.1}"))
+            .collect::<Vec<_>>();
+        writeln!(f, "Percentiles: {}", percentiles.join(", "))?;
+        writeln!(f, "Items per elf: {}", self.items_per_elf)?;
+
+        let most = self.histogram.iter().map(|b| b.elves).max().unwrap_or(0);
+        let width = self
+            .histogram
+            .last()
+            .map_or(0, |bucket| bucket.end.to_string().len());
+        for bucket in &self.histogram {
+            let bar = (bucket.elves * BAR_WIDTH).div_ceil(most.max(1));
+            writeln!(
+                f,
+                "{:>width$} - {:>width$} | {:<BAR_WIDTH$} {}",
+                bucket.start,
+                bucket.end,
+                "#".repeat(bar),
+                bucket.elves
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+    use crate::{elves_from_string, Day1};
+
+    #[test]
+    fn example_stats() {
+        let elves = Day1::parse(Day1::EXAMPLE).unwrap();
+        let stats = stats(&elves, 4).unwrap();
+
+        assert_eq!((stats.elves, stats.total_calories), (5, 55000));
+        assert_eq!((stats.calories.min, stats.calories.max), (4000, 24000));
+        assert_eq!(stats.calories.mean, 11000.);
+        assert_eq!(stats.calories.median, 10000.);
+        assert_eq!(format!("{:.1}", stats.calories.std_dev), "6985.7");
+        assert_eq!(stats.percentiles[1], (25, 6000.));
+        assert_eq!(stats.percentiles[3], (90, 18800.));
+        assert_eq!(stats.items, vec![3, 1, 2, 3, 1]);
+        assert_eq!(stats.items_per_elf.median, 2.);
+        assert_eq!(
+            stats
+                .histogram
+                .iter()
+                .map(|b| (b.start, b.end, b.elves))
+                .collect::<Vec<_>>(),
+            vec![
+                (4000, 8999, 2),
+                (9000, 13999, 2),
+                (14000, 18999, 0),
+                (19000, 24000, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn report() {
+        let elves = elves_from_string("1\n2\n\n4\n\n3").unwrap();
+
+        assert_eq!(
+            stats(&elves, 3).unwrap().to_string(),
+            "\
+3 elves carrying 4 items, 10 calories in total
+Calories: min 3, max 4, mean 3.3, median 3.0, std dev 0.5
+Percentiles: p10 3.0, p25 3.0, p75 3.5, p90 3.8, p99 4.0
+Items per elf: min 1, max 2, mean 1.3, median 1.0, std dev 0.5
+3 - 3 | ######################################## 2
+4 - 4 | ####################                     1
+"
+        );
+    }
+
+    #[test]
+    fn elves_carrying_as_much() {
+        let elves = elves_from_string("5\n\n5").unwrap();
+        let stats = stats(&elves, 10).unwrap();
+
+        assert_eq!(stats.calories.std_dev, 0.);
+        assert_eq!(
+            stats.histogram,
+            vec![Bucket {
+                start: 5,
+                end: 5,
+                elves: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn no_elf() {
+        assert!(stats(&[], 10).is_err());
+        assert!(stats(&[Elf::default()], 0).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+use day_1::Day1;
+
+common::example_tests!(Day1, 24000, 45000);
@@ -0,0 +1,64 @@
+use common::{
+    generator::{Generator, Rng},
+    parse::Mode,
+    Solution, Streaming,
+};
+use day_1::{naive, stream_top_n, top_n, Day1};
+
+#[test]
+fn fast_and_naive_agree() {
+    let mut rng = Rng::new(0x1);
+
+    for size in (0..200).map(|i| 3 + i % 50) {
+        let elves = Day1::parse(&Day1::generate(&mut rng, size)).unwrap();
+
+        assert_eq!(
+            Day1::part_one(&elves).unwrap(),
+            naive::part_one(&elves).unwrap()
+        );
+        assert_eq!(
+            Day1::part_two(&elves).unwrap(),
+            naive::part_two(&elves).unwrap()
+        );
+    }
+}
+
+#[test]
+fn streaming_agrees() {
+    let mut rng = Rng::new(0x11);
+
+    for size in (0..200).map(|i| 3 + i % 50) {
+        let input = Day1::generate(&mut rng, size);
+        let parsed = Day1::parse(&input).unwrap();
+
+        assert_eq!(
+            Day1::stream(&mut input.as_bytes(), Mode::Lenient).unwrap(),
+            (
+                Day1::part_one(&parsed).unwrap(),
+                Day1::part_two(&parsed).unwrap()
+            )
+        );
+    }
+}
+
+#[test]
+fn streamed_top_n_agrees() {
+    let mut rng = Rng::new(0x12);
+
+    for size in (0..200).map(|i| i % 50) {
+        let input = Day1::generate(&mut rng, size);
+        let elves = Day1::parse(&input).unwrap();
+        let k = rng.range(0..=size + 4);
+
+        let top = top_n(&elves, k);
+        let streamed = stream_top_n(&mut input.as_bytes(), k, Mode::Lenient).unwrap();
+        assert_eq!(
+            streamed.elves,
+            top.elves
+                .iter()
+                .map(|(idx, elf)| (*idx, elf.total_calories))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(streamed.total, top.total);
+    }
+}
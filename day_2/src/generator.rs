@@ -0,0 +1,36 @@
+use common::generator::{Generator, Rng};
+
+use crate::Day2;
+
+impl Generator for Day2 {
+    /// A strategy guide of `size` rounds.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                format!(
+                    "{} {}\n",
+                    rng.choose(&['A', 'B', 'C']),
+                    rng.choose(&['X', 'Y', 'Z'])
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{parse::Mode, Solution};
+
+    use super::*;
+
+    #[test]
+    fn generated_input_is_valid() {
+        let mut rng = Rng::new(2);
+
+        for size in [1, 10, 100] {
+            let input = Day2::generate(&mut rng, size);
+
+            assert_eq!(Day2::parse_with(&input, Mode::Strict).unwrap().len(), size);
+        }
+    }
+}
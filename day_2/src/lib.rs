@@ -0,0 +1,227 @@
+pub mod generator;
+pub mod naive;
+
+use std::{io::BufRead, str::FromStr};
+
+use common::{
+    input,
+    parse::{Mode, ParseError, Scanner},
+    Solution, Streaming,
+};
+
+#[derive(Debug, Clone, Eq, PartialEq, Copy)]
+pub enum Move {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Copy)]
+pub enum RoundType {
+    Win,
+    Lose,
+    Draw,
+}
+
+impl FromStr for RoundType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(RoundType::Lose),
+            "Y" => Ok(RoundType::Draw),
+            "Z" => Ok(RoundType::Win),
+            _ => Err(anyhow::anyhow!("Invalid move")),
+        }
+    }
+}
+
+impl Move {
+    pub fn fight(&self, other: &Move) -> u32 {
+        match (self, other) {
+            (a, b) if a == b => 3,
+            (Move::Rock, Move::Scissors) => 0,
+            (Move::Paper, Move::Rock) => 0,
+            (Move::Scissors, Move::Paper) => 0,
+            _ => 6,
+        }
+    }
+
+    pub fn move_resulting_in(&self, rt: RoundType) -> Move {
+        match (rt, self) {
+            (RoundType::Win, Move::Rock) => Move::Paper,
+            (RoundType::Win, Move::Paper) => Move::Scissors,
+            (RoundType::Win, Move::Scissors) => Move::Rock,
+            (RoundType::Lose, Move::Rock) => Move::Scissors,
+            (RoundType::Lose, Move::Paper) => Move::Rock,
+            (RoundType::Lose, Move::Scissors) => Move::Paper,
+            (RoundType::Draw, _) => *self,
+        }
+    }
+
+    pub fn get_score(&self) -> u32 {
+        match self {
+            Move::Rock => 1,
+            Move::Paper => 2,
+            Move::Scissors => 3,
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Move::Rock),
+            "B" => Ok(Move::Paper),
+            "C" => Ok(Move::Scissors),
+            "X" => Ok(Move::Rock),
+            "Y" => Ok(Move::Paper),
+            "Z" => Ok(Move::Scissors),
+            _ => Err(anyhow::anyhow!("invalid move")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Round {
+    pub play: Move,
+    pub response: Move,
+    pub round_type: RoundType,
+}
+
+impl Round {
+    /// Score when the second column is read as the move to play.
+    pub fn guessed_score(&self) -> u32 {
+        self.play.fight(&self.response) + self.response.get_score()
+    }
+
+    /// Score when the second column is read as the expected outcome.
+    pub fn score(&self) -> u32 {
+        let mut score = 0;
+        let response = self.play.move_resulting_in(self.round_type);
+        score += self.play.fight(&response);
+        score += response.get_score();
+        score
+    }
+}
+
+impl FromStr for Round {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Scanner::whole(s, |s| {
+            s.whitespace();
+            let play = s.one_of(
+                &[("A", Move::Rock), ("B", Move::Paper), ("C", Move::Scissors)],
+                "an opponent move among A, B or C",
+            )?;
+
+            // The second column is read both as a move and as an outcome.
+            let (response, round_type) = s.one_of(
+                &[
+                    ("X", (Move::Rock, RoundType::Lose)),
+                    ("Y", (Move::Paper, RoundType::Draw)),
+                    ("Z", (Move::Scissors, RoundType::Win)),
+                ],
+                "a response among X, Y or Z",
+            )?;
+
+            Ok(Round {
+                play,
+                response,
+                round_type,
+            })
+        })
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const EXAMPLE: &'static str = include_str!("../example.input.txt");
+
+    type Input = Vec<Round>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, mode: Mode) -> anyhow::Result<Self::Input> {
+        Ok(mode.collect(input.lines().enumerate(), Round::from_str)?)
+    }
+
+    fn part_one(rounds: &Self::Input) -> anyhow::Result<u64> {
+        Ok(rounds
+            .iter()
+            .map(|round| u64::from(round.guessed_score()))
+            .sum())
+    }
+
+    fn part_two(rounds: &Self::Input) -> anyhow::Result<u64> {
+        Ok(rounds.iter().map(|round| u64::from(round.score())).sum())
+    }
+}
+
+impl Streaming for Day2 {
+    fn stream(reader: &mut dyn BufRead, mode: Mode) -> anyhow::Result<(u64, u64)> {
+        let (mut guessed, mut total) = (0, 0);
+
+        input::for_each_line(reader, |idx, line| {
+            if let Some(round) = mode.line(idx, line, Round::from_str)? {
+                guessed += u64::from(round.guessed_score());
+                total += u64::from(round.score());
+            }
+            Ok(())
+        })?;
+
+        Ok((guessed, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_resulting_in() {
+        assert_eq!(Move::Rock.move_resulting_in(RoundType::Win), Move::Paper);
+        assert_eq!(Move::Paper.move_resulting_in(RoundType::Lose), Move::Rock);
+        assert_eq!(
+            Move::Scissors.move_resulting_in(RoundType::Draw),
+            Move::Scissors
+        );
+    }
+
+    #[test]
+    fn fight_scores_the_response() {
+        assert_eq!(Move::Rock.fight(&Move::Paper), 6);
+        assert_eq!(Move::Rock.fight(&Move::Rock), 3);
+        assert_eq!(Move::Rock.fight(&Move::Scissors), 0);
+    }
+
+    #[test]
+    fn round_scores() {
+        let round = Round::from_str("A Y").unwrap();
+
+        assert_eq!(round.guessed_score(), 8);
+        assert_eq!(round.score(), 4);
+    }
+
+    #[test]
+    fn invalid_round() {
+        assert_eq!(Round::from_str("A").unwrap_err().column, 2);
+        assert_eq!(Round::from_str("D X").unwrap_err().column, 1);
+        assert_eq!(Round::from_str("A  A").unwrap_err().column, 4);
+        assert_eq!(Round::from_str("A X Y").unwrap_err().column, 5);
+    }
+
+    #[test]
+    fn strict_mode() {
+        let input = "A Y\nB\nC Z\n";
+
+        assert_eq!(Day2::parse(input).unwrap().len(), 2);
+        let err = Day2::parse_with(input, Mode::Strict).unwrap_err();
+        assert_eq!(err.downcast_ref::<ParseError>().unwrap().line, 2);
+    }
+}
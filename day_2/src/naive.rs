@@ -0,0 +1,58 @@
+//! Straightforward implementations, kept as reference for the optimized ones.
+
+use crate::{Move, Round, RoundType};
+
+const MOVES: [Move; 3] = [Move::Rock, Move::Paper, Move::Scissors];
+
+/// The move beating `m`, straight from the rules of the game.
+fn beaten_by(m: Move) -> Move {
+    match m {
+        Move::Rock => Move::Paper,
+        Move::Paper => Move::Scissors,
+        Move::Scissors => Move::Rock,
+    }
+}
+
+fn outcome(play: Move, response: Move) -> RoundType {
+    if play == response {
+        RoundType::Draw
+    } else if beaten_by(play) == response {
+        RoundType::Win
+    } else {
+        RoundType::Lose
+    }
+}
+
+fn score(play: Move, response: Move) -> u32 {
+    let outcome = match outcome(play, response) {
+        RoundType::Lose => 0,
+        RoundType::Draw => 3,
+        RoundType::Win => 6,
+    };
+    let shape = MOVES.iter().position(|m| *m == response).unwrap() as u32 + 1;
+
+    outcome + shape
+}
+
+/// Try every move until one gives the wanted outcome.
+pub fn move_resulting_in(play: Move, round_type: RoundType) -> Move {
+    MOVES
+        .into_iter()
+        .find(|response| outcome(play, *response) == round_type)
+        .expect("every outcome is reachable")
+}
+
+pub fn part_one(rounds: &[Round]) -> anyhow::Result<u64> {
+    Ok(rounds
+        .iter()
+        .map(|round| u64::from(score(round.play, round.response)))
+        .sum())
+}
+
+pub fn part_two(rounds: &[Round]) -> anyhow::Result<u64> {
+    Ok(rounds
+        .iter()
+        .map(|round| score(round.play, move_resulting_in(round.play, round.round_type)))
+        .map(u64::from)
+        .sum())
+}
@@ -0,0 +1,3 @@
+use day_2::Day2;
+
+common::example_tests!(Day2, 15, 12);
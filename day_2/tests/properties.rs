@@ -0,0 +1,54 @@
+use common::{
+    generator::{Generator, Rng},
+    parse::Mode,
+    Solution, Streaming,
+};
+use day_2::{naive, Day2, Move, RoundType};
+
+#[test]
+fn move_resulting_in_agrees() {
+    for play in [Move::Rock, Move::Paper, Move::Scissors] {
+        for round_type in [RoundType::Win, RoundType::Lose, RoundType::Draw] {
+            assert_eq!(
+                play.move_resulting_in(round_type),
+                naive::move_resulting_in(play, round_type)
+            );
+        }
+    }
+}
+
+#[test]
+fn fast_and_naive_agree() {
+    let mut rng = Rng::new(0x2);
+
+    for size in 0..100 {
+        let rounds = Day2::parse(&Day2::generate(&mut rng, size)).unwrap();
+
+        assert_eq!(
+            Day2::part_one(&rounds).unwrap(),
+            naive::part_one(&rounds).unwrap()
+        );
+        assert_eq!(
+            Day2::part_two(&rounds).unwrap(),
+            naive::part_two(&rounds).unwrap()
+        );
+    }
+}
+
+#[test]
+fn streaming_agrees() {
+    let mut rng = Rng::new(0x22);
+
+    for size in 0..100 {
+        let input = Day2::generate(&mut rng, size);
+        let parsed = Day2::parse(&input).unwrap();
+
+        assert_eq!(
+            Day2::stream(&mut input.as_bytes(), Mode::Lenient).unwrap(),
+            (
+                Day2::part_one(&parsed).unwrap(),
+                Day2::part_two(&parsed).unwrap()
+            )
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use common::generator::{Generator, Rng};
+
+use crate::Day3;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl Generator for Day3 {
+    /// `size` groups of three rucksacks. Every rucksack has exactly one item
+    /// type in both compartments and every group exactly one badge.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut out = String::new();
+
+        for _ in 0..size {
+            let mut items = ITEMS.to_vec();
+            rng.shuffle(&mut items);
+            let badge = items.pop().expect("there are more than one item type");
+
+            // Each elf of the group only picks from its own 17 item types,
+            // so the badge is the only one carried by the three of them.
+            for pool in items.chunks(items.len() / 3) {
+                out.push_str(&rucksack(rng, badge, pool));
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+}
+
+/// Rucksack holding `badge` once and sharing a single item type of `pool`
+/// between its compartments.
+fn rucksack(rng: &mut Rng, badge: u8, pool: &[u8]) -> String {
+    let (shared, pool) = pool.split_first().expect("pool is not empty");
+    let (left_only, right_only) = pool.split_at(pool.len() / 2);
+    let compartment_size = rng.range(2..=16);
+
+    let mut left = vec![*shared];
+    let mut right = vec![*shared];
+    if rng.chance(0.5) {
+        left.push(badge);
+    } else {
+        right.push(badge);
+    }
+
+    for (compartment, items) in [(&mut left, left_only), (&mut right, right_only)] {
+        while compartment.len() < compartment_size {
+            compartment.push(*rng.choose(items));
+        }
+        rng.shuffle(compartment);
+    }
+
+    left.into_iter().chain(right).map(char::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use common::{parse::Mode, Solution};
+
+    use super::*;
+
+    #[test]
+    fn one_shared_item_and_one_badge() {
+        let mut rng = Rng::new(3);
+        let input = Day3::generate(&mut rng, 50);
+        let packs = Day3::parse_with(&input, Mode::Strict).unwrap();
+        assert_eq!(packs.len(), 150);
+
+        for pack in &packs {
+            assert_eq!(pack.find_common_inside().len(), 1, "{pack}");
+        }
+
+        for group in packs.chunks(3) {
+            let items = group
+                .iter()
+                .map(|pack| pack.to_string().chars().collect::<HashSet<_>>())
+                .collect::<Vec<_>>();
+            let badges = items[0]
+                .iter()
+                .filter(|c| items[1].contains(c) && items[2].contains(c))
+                .count();
+            assert_eq!(badges, 1);
+        }
+    }
+}
@@ -0,0 +1,262 @@
+pub mod generator;
+pub mod naive;
+
+use anyhow::Context;
+use common::{
+    input,
+    parse::{Mode, ParseError, Scanner},
+    Solution, Streaming,
+};
+use std::{fmt::Display, io::BufRead, str::FromStr};
+
+#[derive(Debug, Clone)]
+pub struct Backpack {
+    pub part_1: Vec<char>,
+    pub part_2: Vec<char>,
+}
+
+impl Backpack {
+    pub fn count_char(&self, c: char) -> usize {
+        self.part_1.iter().filter(|e| *e == &c).count()
+            + self.part_2.iter().filter(|e| *e == &c).count()
+    }
+}
+
+impl Display for Backpack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            self.part_1.iter().collect::<String>(),
+            self.part_2.iter().collect::<String>(),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub one: Backpack,
+    pub two: Backpack,
+    pub three: Backpack,
+    pub badge: char,
+}
+
+impl Group {
+    pub fn new(one: Backpack, two: Backpack, three: Backpack) -> Self {
+        let common_one_and_two = one.find_common(&two);
+
+        let badge = three
+            .find_uniq(&common_one_and_two)
+            .expect("invalid input, no uniq char found");
+
+        Group {
+            one,
+            two,
+            three,
+            badge,
+        }
+    }
+}
+
+impl Backpack {
+    pub fn find_common_inside(&self) -> Vec<char> {
+        let mut common = Vec::new();
+
+        for c in 'A'..='z' {
+            if self.part_1.contains(&c) && self.part_2.contains(&c) {
+                common.push(c);
+            }
+        }
+        common
+    }
+
+    pub fn find_common(&self, other: &Self) -> Vec<char> {
+        let mut common = Vec::new();
+
+        for c in 'A'..='z' {
+            if (self.part_1.contains(&c) || self.part_2.contains(&c))
+                && (other.part_1.contains(&c) || other.part_2.contains(&c))
+            {
+                common.push(c);
+            }
+        }
+
+        common
+    }
+
+    /// Items of a compartment as a bit set, where bit `n` is the item valued `n`.
+    fn mask(compartment: &[char]) -> u64 {
+        compartment
+            .iter()
+            .fold(0, |mask, c| mask | 1 << get_value(*c))
+    }
+
+    /// Items found in both compartments.
+    pub fn common_inside_mask(&self) -> u64 {
+        Self::mask(&self.part_1) & Self::mask(&self.part_2)
+    }
+
+    /// Every item of the backpack.
+    pub fn items_mask(&self) -> u64 {
+        Self::mask(&self.part_1) | Self::mask(&self.part_2)
+    }
+
+    pub fn find_uniq(&self, list: &[char]) -> Option<char> {
+        for c in list {
+            if self.part_1.contains(c) || self.part_2.contains(c) {
+                return Some(*c);
+            }
+        }
+
+        None
+    }
+}
+
+impl FromStr for Backpack {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Scanner::whole(s, |s| {
+            let mut part_1 =
+                s.many(|s| s.char("an item between a-z or A-Z", |c| c.is_ascii_alphabetic()))?;
+            if part_1.is_empty() || !part_1.len().is_multiple_of(2) {
+                return Err(s.error("an even number of items, split between both compartments"));
+            }
+
+            let part_2 = part_1.split_off(part_1.len() / 2);
+            Ok(Backpack { part_1, part_2 })
+        })
+    }
+}
+
+pub fn get_value(c: char) -> u32 {
+    if c.is_lowercase() {
+        (c as u32) - 96
+    } else {
+        (c as u32) - 38
+    }
+}
+
+/// Sum of the values of every item in the set.
+fn value_of_mask(mut mask: u64) -> u64 {
+    let mut sum = 0;
+
+    while mask != 0 {
+        sum += u64::from(mask.trailing_zeros());
+        mask &= mask - 1;
+    }
+    sum
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const EXAMPLE: &'static str = include_str!("../example.input.txt");
+
+    type Input = Vec<Backpack>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, mode: Mode) -> anyhow::Result<Self::Input> {
+        Ok(mode.collect(input.lines().enumerate(), Backpack::from_str)?)
+    }
+
+    fn part_one(packs: &Self::Input) -> anyhow::Result<u64> {
+        Ok(packs
+            .iter()
+            .map(|pack| value_of_mask(pack.common_inside_mask()))
+            .sum())
+    }
+
+    fn part_two(packs: &Self::Input) -> anyhow::Result<u64> {
+        packs
+            .chunks(3)
+            .enumerate()
+            .map(|(n, chunk)| match chunk {
+                [one, two, three] => badge(n, [one, two, three].map(Backpack::items_mask)),
+                _ => badge(n, [0]),
+            })
+            .sum()
+    }
+}
+
+/// Value of the badge of the `n`th group, the only item carried by all of
+/// its elves.
+fn badge(n: usize, items: impl IntoIterator<Item = u64>) -> anyhow::Result<u64> {
+    match items
+        .into_iter()
+        .fold(u64::MAX, |common, items| common & items)
+    {
+        0 => None,
+        badge => Some(u64::from(badge.trailing_zeros())),
+    }
+    .with_context(|| format!("No badge for group {}", n + 1))
+}
+
+impl Streaming for Day3 {
+    /// Only keep the items of the current group.
+    fn stream(reader: &mut dyn BufRead, mode: Mode) -> anyhow::Result<(u64, u64)> {
+        let (mut common, mut badges) = (0, 0);
+        let mut groups = 0;
+        let mut group = Vec::with_capacity(3);
+
+        input::for_each_line(reader, |idx, line| {
+            let Some(pack) = mode.line(idx, line, Backpack::from_str)? else {
+                return Ok(());
+            };
+
+            common += value_of_mask(pack.common_inside_mask());
+            group.push(pack.items_mask());
+            if group.len() == 3 {
+                badges += badge(groups, group.drain(..))?;
+                groups += 1;
+            }
+            Ok(())
+        })?;
+
+        if !group.is_empty() {
+            badge(groups, [0])?;
+        }
+
+        Ok((common, badges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_value() {
+        assert_eq!(super::get_value('a'), 1);
+        assert_eq!(super::get_value('z'), 26);
+        assert_eq!(super::get_value('A'), 27);
+        assert_eq!(super::get_value('Z'), 52);
+    }
+
+    #[test]
+    fn find_common_inside() {
+        let pack = Backpack::from_str("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+
+        assert_eq!(pack.find_common_inside(), vec!['p']);
+    }
+
+    #[test]
+    fn invalid_backpack() {
+        assert_eq!(Backpack::from_str("ab1d").unwrap_err().column, 3);
+        assert_eq!(Backpack::from_str("abc").unwrap_err().column, 4);
+        assert!(Backpack::from_str("").is_err());
+    }
+
+    #[test]
+    fn group_badge() {
+        let group = Group::new(
+            Backpack::from_str("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap(),
+            Backpack::from_str("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL").unwrap(),
+            Backpack::from_str("PmmdzqPrVvPwwTWBwg").unwrap(),
+        );
+
+        assert_eq!(group.badge, 'r');
+    }
+}
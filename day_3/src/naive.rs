@@ -0,0 +1,25 @@
+//! Straightforward implementations, kept as reference for the optimized ones.
+
+use anyhow::bail;
+
+use crate::{get_value, Backpack, Group};
+
+pub fn part_one(packs: &[Backpack]) -> anyhow::Result<u64> {
+    Ok(packs
+        .iter()
+        .flat_map(|pack| pack.find_common_inside())
+        .map(|c| u64::from(get_value(c)))
+        .sum())
+}
+
+pub fn part_two(packs: &[Backpack]) -> anyhow::Result<u64> {
+    packs
+        .chunks(3)
+        .enumerate()
+        .map(|(n, chunk)| match chunk {
+            [one, two, three] => Ok(Group::new(one.clone(), two.clone(), three.clone())),
+            _ => bail!("Group {} has fewer than three elves", n + 1),
+        })
+        .map(|group| Ok(u64::from(get_value(group?.badge))))
+        .sum()
+}
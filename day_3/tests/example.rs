@@ -0,0 +1,3 @@
+use day_3::Day3;
+
+common::example_tests!(Day3, 157, 70);
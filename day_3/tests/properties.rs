@@ -0,0 +1,51 @@
+use common::{
+    generator::{Generator, Rng},
+    parse::Mode,
+    Solution, Streaming,
+};
+use day_3::{naive, Day3};
+
+#[test]
+fn fast_and_naive_agree() {
+    let mut rng = Rng::new(0x3);
+
+    for size in 0..100 {
+        let packs = Day3::parse(&Day3::generate(&mut rng, size)).unwrap();
+
+        assert_eq!(
+            Day3::part_one(&packs).unwrap(),
+            naive::part_one(&packs).unwrap()
+        );
+        assert_eq!(
+            Day3::part_two(&packs).unwrap(),
+            naive::part_two(&packs).unwrap()
+        );
+    }
+}
+
+#[test]
+fn incomplete_group() {
+    let packs =
+        Day3::parse("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n").unwrap();
+
+    assert!(Day3::part_two(&packs).is_err());
+    assert!(naive::part_two(&packs).is_err());
+}
+
+#[test]
+fn streaming_agrees() {
+    let mut rng = Rng::new(0x33);
+
+    for size in 0..100 {
+        let input = Day3::generate(&mut rng, size);
+        let parsed = Day3::parse(&input).unwrap();
+
+        assert_eq!(
+            Day3::stream(&mut input.as_bytes(), Mode::Lenient).unwrap(),
+            (
+                Day3::part_one(&parsed).unwrap(),
+                Day3::part_two(&parsed).unwrap()
+            )
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use common::generator::{Generator, Rng};
+
+use crate::Day4;
+
+impl Generator for Day4 {
+    /// `size` pairs of section assignments between 1 and 99.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| format!("{},{}\n", range(rng), range(rng)))
+            .collect()
+    }
+}
+
+fn range(rng: &mut Rng) -> String {
+    let start = rng.range(1..=99);
+    format!("{start}-{}", rng.range(start..=99))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{parse::Mode, Solution};
+
+    use super::*;
+
+    #[test]
+    fn generated_input_is_valid() {
+        let mut rng = Rng::new(4);
+        let input = Day4::generate(&mut rng, 100);
+        let groups = Day4::parse_with(&input, Mode::Strict).unwrap();
+
+        assert_eq!(groups.len(), 100);
+        assert!(groups.iter().all(|g| g.first.start <= g.first.end));
+    }
+}
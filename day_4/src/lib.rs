@@ -0,0 +1,146 @@
+pub mod generator;
+pub mod naive;
+
+use common::{
+    input,
+    parse::{Mode, ParseError, Scanner},
+    Solution, Streaming,
+};
+use std::{io::BufRead, ops::Range, str::FromStr};
+
+#[derive(Debug, Default, Clone)]
+pub struct Group {
+    pub first: Box<Range<usize>>,
+    pub second: Box<Range<usize>>,
+}
+
+impl Group {
+    pub fn one_contains_the_other(&self) -> bool {
+        fully_contains(&self.first, &self.second) || fully_contains(&self.second, &self.first)
+    }
+
+    pub fn overlaping(&self) -> bool {
+        (self.first.start <= self.second.start && self.first.end >= self.second.start)
+            || (self.second.start <= self.first.start && self.second.end >= self.first.start)
+    }
+}
+
+pub fn fully_contains(this: &Range<usize>, other: &Range<usize>) -> bool {
+    this.start <= other.start && this.end >= other.end
+}
+
+impl FromStr for Group {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        Scanner::whole(line, |s| {
+            let mut ranges = 0;
+            let ranges = s.separated(",", |s| {
+                ranges += 1;
+                match ranges {
+                    1 | 2 => range(s),
+                    _ => Err(ParseError::new(line, s.offset() - 1, "the end of the line")),
+                }
+            })?;
+
+            match <[_; 2]>::try_from(ranges) {
+                Ok([first, second]) => Ok(Group { first, second }),
+                Err(_) => Err(s.error("`,` between the two ranges")),
+            }
+        })
+    }
+}
+
+/// A range written `<start>-<end>`, `start` being at most `end`.
+fn range(s: &mut Scanner) -> Result<Box<Range<usize>>, ParseError> {
+    let at = *s;
+    let start = s.unsigned("an unsigned integer")?;
+    s.literal("-")
+        .map_err(|_| s.error("`-` between the bounds of a range"))?;
+    let end = s.unsigned("an unsigned integer")?;
+    if start > end {
+        return Err(at.error(format!("a range starting at most at its end, {end}")));
+    }
+
+    Ok(Box::new(start..end))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const EXAMPLE: &'static str = include_str!("../example.input.txt");
+
+    type Input = Vec<Group>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse_with(input: &str, mode: Mode) -> anyhow::Result<Self::Input> {
+        Ok(mode.collect(input.lines().enumerate(), Group::from_str)?)
+    }
+
+    fn part_one(groups: &Self::Input) -> anyhow::Result<usize> {
+        Ok(groups
+            .iter()
+            .filter(|group| group.one_contains_the_other())
+            .count())
+    }
+
+    fn part_two(groups: &Self::Input) -> anyhow::Result<usize> {
+        Ok(groups.iter().filter(|group| group.overlaping()).count())
+    }
+}
+
+impl Streaming for Day4 {
+    fn stream(reader: &mut dyn BufRead, mode: Mode) -> anyhow::Result<(usize, usize)> {
+        let (mut contained, mut overlaping) = (0, 0);
+
+        input::for_each_line(reader, |idx, line| {
+            if let Some(group) = mode.line(idx, line, Group::from_str)? {
+                contained += usize::from(group.one_contains_the_other());
+                overlaping += usize::from(group.overlaping());
+            }
+            Ok(())
+        })?;
+
+        Ok((contained, overlaping))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fully_contains() {
+        assert!(super::fully_contains(&(2..8), &(3..7)));
+        assert!(super::fully_contains(&(4..6), &(6..6)));
+        assert!(!super::fully_contains(&(3..7), &(2..8)));
+    }
+
+    #[test]
+    fn overlaping() {
+        assert!(Group::from_str("5-7,7-9").unwrap().overlaping());
+        assert!(Group::from_str("2-8,3-7").unwrap().overlaping());
+        assert!(!Group::from_str("2-4,6-8").unwrap().overlaping());
+    }
+
+    #[test]
+    fn invalid_group() {
+        assert_eq!(Group::from_str("2-4").unwrap_err().column, 4);
+        assert_eq!(Group::from_str("2-4,68").unwrap_err().column, 7);
+        assert_eq!(Group::from_str("2-x,6-8").unwrap_err().column, 3);
+        assert_eq!(Group::from_str("2-4,-8").unwrap_err().column, 5);
+        assert_eq!(Group::from_str("2-4,6-8,9-9").unwrap_err().column, 8);
+        assert_eq!(Group::from_str("7-3,1-2").unwrap_err().column, 1);
+        assert_eq!(Group::from_str("1-2,17-3").unwrap_err().column, 5);
+    }
+
+    #[test]
+    fn parse_group() {
+        let group = Group::from_str("2-4,6-8").unwrap();
+
+        assert_eq!(*group.first, 2..4);
+        assert_eq!(*group.second, 6..8);
+    }
+}
@@ -1,72 +1,13 @@
-#![allow(dead_code)]
-use anyhow::Context;
-use std::{ops::Range, str::FromStr};
-
-#[derive(Debug, Default, Clone)]
-struct Group {
-    first: Box<Range<usize>>,
-    second: Box<Range<usize>>,
-}
-
-impl Group {
-    fn one_contains_the_other(&self) -> bool {
-        fully_contains(&self.first, &self.second) || fully_contains(&self.second, &self.first)
-    }
-
-    fn overlaping(&self) -> bool {
-        (self.first.start <= self.second.start && self.first.end >= self.second.start)
-            || (self.second.start <= self.first.start && self.second.end >= self.first.start)
-    }
-}
-
-fn fully_contains(this: &Range<usize>, other: &Range<usize>) -> bool {
-    this.start <= other.start && this.end >= other.end
-}
-
-impl FromStr for Group {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut splitted = s
-            .split(',')
-            .map(|range| {
-                let splitted_range = range
-                    .split('-')
-                    .map(|n| n.parse::<usize>())
-                    .filter_map(Result::ok)
-                    .collect::<Vec<usize>>();
-
-                Box::new(splitted_range[0]..splitted_range[1])
-            })
-            .collect::<Vec<_>>();
-
-        let second = splitted.pop().context("No last range")?;
-        let first = splitted.pop().context("No first range")?;
-
-        Ok(Group { first, second })
-    }
-}
-
-const DEFAULT_INPUT_PATH: &'static str = "input.txt";
+use common::Solution;
+use day_4::Day4;
 
 fn main() -> anyhow::Result<()> {
-    let mut args = std::env::args();
-    let file_path = args.nth(1).unwrap_or(DEFAULT_INPUT_PATH.to_string());
-    let buf = std::fs::read_to_string(file_path)?;
-
-    let groups = buf
-        .lines()
-        .map(Group::from_str)
-        .filter_map(Result::ok)
-        .collect::<Vec<Group>>();
+    let buf = common::read_input::<Day4>()?;
 
-    let count = groups
-        .iter()
-        .map(|group| group.overlaping())
-        .filter(|&bool| bool)
-        .count();
+    let groups = Day4::parse(&buf)?;
 
-    println!("group: {count}");
+    println!("fully contained: {}", Day4::part_one(&groups)?);
+    println!("overlapping: {}", Day4::part_two(&groups)?);
 
     Ok(())
 }
@@ -0,0 +1,34 @@
+//! Straightforward implementations, kept as reference for the optimized ones.
+
+use std::{collections::HashSet, ops::Range};
+
+use crate::Group;
+
+/// Every section of an assignment, its end being inclusive.
+fn sections(range: &Range<usize>) -> HashSet<usize> {
+    (range.start..=range.end).collect()
+}
+
+pub fn one_contains_the_other(group: &Group) -> bool {
+    let (first, second) = (sections(&group.first), sections(&group.second));
+
+    first.is_subset(&second) || second.is_subset(&first)
+}
+
+pub fn overlaping(group: &Group) -> bool {
+    sections(&group.first)
+        .intersection(&sections(&group.second))
+        .next()
+        .is_some()
+}
+
+pub fn part_one(groups: &[Group]) -> anyhow::Result<usize> {
+    Ok(groups
+        .iter()
+        .filter(|group| one_contains_the_other(group))
+        .count())
+}
+
+pub fn part_two(groups: &[Group]) -> anyhow::Result<usize> {
+    Ok(groups.iter().filter(|group| overlaping(group)).count())
+}
@@ -0,0 +1,3 @@
+use day_4::Day4;
+
+common::example_tests!(Day4, 2, 4);
@@ -0,0 +1,50 @@
+use common::{
+    generator::{Generator, Rng},
+    parse::Mode,
+    Solution, Streaming,
+};
+use day_4::{naive, Day4};
+
+#[test]
+fn fast_and_naive_agree() {
+    let mut rng = Rng::new(0x4);
+
+    for size in 0..100 {
+        let groups = Day4::parse(&Day4::generate(&mut rng, size)).unwrap();
+
+        for group in &groups {
+            assert_eq!(group.overlaping(), naive::overlaping(group), "{group:?}");
+            assert_eq!(
+                group.one_contains_the_other(),
+                naive::one_contains_the_other(group),
+                "{group:?}"
+            );
+        }
+        assert_eq!(
+            Day4::part_one(&groups).unwrap(),
+            naive::part_one(&groups).unwrap()
+        );
+        assert_eq!(
+            Day4::part_two(&groups).unwrap(),
+            naive::part_two(&groups).unwrap()
+        );
+    }
+}
+
+#[test]
+fn streaming_agrees() {
+    let mut rng = Rng::new(0x44);
+
+    for size in 0..100 {
+        let input = Day4::generate(&mut rng, size);
+        let parsed = Day4::parse(&input).unwrap();
+
+        assert_eq!(
+            Day4::stream(&mut input.as_bytes(), Mode::Lenient).unwrap(),
+            (
+                Day4::part_one(&parsed).unwrap(),
+                Day4::part_two(&parsed).unwrap()
+            )
+        );
+    }
+}
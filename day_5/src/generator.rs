@@ -0,0 +1,90 @@
+use common::generator::{Generator, Rng};
+
+use crate::Day5;
+
+const MAX_STACKS: usize = 9;
+
+impl Generator for Day5 {
+    /// A drawing of up to 9 stacks followed by `size` moves, each of them
+    /// legal under both crane models.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let nb_stack = rng.range(2..=MAX_STACKS);
+        let mut stacks = (0..nb_stack)
+            .map(|_| {
+                (0..rng.range(0..=8))
+                    .map(|_| char::from(rng.range(b'A' as usize..=b'Z' as usize) as u8))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        if stacks.iter().all(Vec::is_empty) {
+            stacks[0].push('A');
+        }
+
+        let mut out = drawing(&stacks);
+        out.push('\n');
+
+        for _ in 0..size {
+            let filled = (0..nb_stack)
+                .filter(|&idx| !stacks[idx].is_empty())
+                .collect::<Vec<_>>();
+            let from = *rng.choose(&filled);
+            let to = (from + rng.range(1..=nb_stack - 1)) % nb_stack;
+            let nb = rng.range(1..=stacks[from].len());
+
+            let at = stacks[from].len() - nb;
+            let moved = stacks[from].split_off(at);
+            stacks[to].extend(moved);
+
+            out.push_str(&format!("move {nb} from {} to {}\n", from + 1, to + 1));
+        }
+
+        out
+    }
+}
+
+/// Drawing of `stacks`, with the stack numbers below them.
+fn drawing(stacks: &[Vec<char>]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut out = String::new();
+
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(c) => format!("[{c}]"),
+                None => "   ".to_owned(),
+            })
+            .collect::<Vec<_>>();
+        out.push_str(&row.join(" "));
+        out.push('\n');
+    }
+
+    let numbers = (1..=stacks.len())
+        .map(|idx| format!(" {idx} "))
+        .collect::<Vec<_>>();
+    out.push_str(&numbers.join(" "));
+    out.push('\n');
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{parse::Mode, Solution};
+
+    use super::*;
+
+    #[test]
+    fn moves_are_legal() {
+        let mut rng = Rng::new(5);
+
+        for size in [0, 1, 50, 500] {
+            let input = Day5::generate(&mut rng, size);
+            let procedure = Day5::parse_with(&input, Mode::Strict).unwrap();
+
+            assert_eq!(procedure.movements.len(), size);
+            Day5::part_one(&procedure).unwrap();
+            Day5::part_two(&procedure).unwrap();
+        }
+    }
+}
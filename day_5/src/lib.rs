@@ -0,0 +1,368 @@
+pub mod generator;
+pub mod naive;
+pub mod visual;
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use anyhow::Context;
+use common::{
+    parse::{self, Mode, ParseError, Scanner},
+    Solution,
+};
+
+#[derive(Debug, Clone)]
+pub struct Stack {
+    pub height: usize,
+    pub width: usize,
+    pub inner: Vec<Vec<char>>,
+}
+
+impl Stack {
+    /// Parse the drawing of the stacks, `input` being the first lines of
+    /// the puzzle input.
+    pub fn new(input: &[&str]) -> Result<Self, ParseError> {
+        let max_stack_height = input.len();
+        let nb_stack = input
+            .iter()
+            .map(|line| (line.chars().count() + 1) / 4)
+            .max()
+            .unwrap_or(0);
+
+        let mut stacks: Vec<Vec<char>> = vec![Vec::with_capacity(max_stack_height); nb_stack];
+
+        for (idx, line) in input.iter().enumerate().rev() {
+            for (x, (offset, column)) in parse::columns(line, 4).enumerate() {
+                if column.trim().is_empty() {
+                    continue;
+                }
+
+                let end = offset + column.len();
+                let drawn = || {
+                    let mut s = Scanner::at(line, offset);
+                    s.literal("[")?;
+                    let c = s.char("a crate", |c| c != ']')?;
+                    s.literal("]")?;
+                    Ok::<_, ParseError>((c, s.offset()))
+                };
+                let c = match drawn() {
+                    Ok((c, after)) if x < nb_stack && line[after..end].trim().is_empty() => c,
+                    _ => {
+                        return Err(
+                            ParseError::new(line, offset, "a crate `[X]` or an empty slot")
+                                .on_line(idx + 1),
+                        )
+                    }
+                };
+                stacks[x].push(c);
+            }
+        }
+
+        Ok(Self {
+            inner: stacks,
+            height: max_stack_height,
+            width: nb_stack,
+        })
+    }
+
+    pub fn r#move(&mut self, m: Movement) -> anyhow::Result<()> {
+        let from = self.check(m)?;
+        if m.from == m.to {
+            return Ok(());
+        }
+
+        let crates = from.split_off(from.len() - m.nb);
+        self.stack(m.to)?.extend(crates);
+
+        Ok(())
+    }
+
+    /// Move crates one at a time, reversing their order on arrival.
+    pub fn move_one_by_one(&mut self, m: Movement) -> anyhow::Result<()> {
+        self.check(m)?;
+
+        for _ in 0..m.nb {
+            self.r#move(Movement { nb: 1, ..m })?;
+        }
+
+        Ok(())
+    }
+
+    /// Same as [`Stack::move_one_by_one`], moving every crate at once.
+    pub fn move_reversed(&mut self, m: Movement) -> anyhow::Result<()> {
+        let from = self.check(m)?;
+        if m.from == m.to {
+            return Ok(());
+        }
+
+        let crates = from.split_off(from.len() - m.nb);
+        self.stack(m.to)?.extend(crates.into_iter().rev());
+
+        Ok(())
+    }
+
+    /// Stack to move from, once checked that both stacks of `m` exist and
+    /// that there are enough crates to move.
+    fn check(&mut self, m: Movement) -> anyhow::Result<&mut Vec<char>> {
+        self.stack(m.to)?;
+        let from = self.stack(m.from)?;
+        if from.len() < m.nb {
+            anyhow::bail!(
+                "Cannot move {} from {} to {} no enough box",
+                m.nb,
+                m.from,
+                m.to
+            );
+        }
+
+        Ok(from)
+    }
+
+    /// Stack `n`, stacks being numbered from 1.
+    fn stack(&mut self, n: usize) -> anyhow::Result<&mut Vec<char>> {
+        let width = self.inner.len();
+        n.checked_sub(1)
+            .and_then(|idx| self.inner.get_mut(idx))
+            .with_context(|| format!("No stack {n}, only stacks 1 to {width} exist"))
+    }
+
+    /// Crates on top of each non empty stack.
+    pub fn tops(&self) -> String {
+        self.inner.iter().filter_map(|s| s.last()).collect()
+    }
+}
+
+impl Display for Stack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, stack) in self.inner.iter().enumerate() {
+            writeln!(f, "{} -> {:?}", idx + 1, stack)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Movement {
+    pub nb: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl FromStr for Movement {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, None)
+    }
+}
+
+impl Movement {
+    /// Parse `s`, checking both stacks are among the `width` drawn ones.
+    pub fn within(s: &str, width: usize) -> Result<Self, ParseError> {
+        Self::parse(s, Some(width))
+    }
+
+    fn parse(s: &str, width: Option<usize>) -> Result<Self, ParseError> {
+        let stack = |s: &mut Scanner, expected: &str| {
+            let start = *s;
+            match s.unsigned(expected)? {
+                stack if width.is_some_and(|width| !(1..=width).contains(&stack)) => {
+                    let width = width.unwrap_or_default();
+                    Err(start.error(format!("a stack between 1 and {width}")))
+                }
+                stack => Ok(stack),
+            }
+        };
+
+        Scanner::whole(s, |s| {
+            s.keyword("move")?;
+            let nb = s.unsigned("a number of crates")?;
+            s.keyword("from")?;
+            let from = stack(s, "a stack to move from")?;
+            s.keyword("to")?;
+            let to = stack(s, "a stack to move to")?;
+
+            Ok(Movement { nb, from, to })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Procedure {
+    pub stack: Stack,
+    pub movements: Vec<Movement>,
+}
+
+impl Procedure {
+    pub fn rearrange(
+        &self,
+        mut apply: impl FnMut(&mut Stack, Movement) -> anyhow::Result<()>,
+    ) -> anyhow::Result<String> {
+        let mut stack = self.stack.clone();
+
+        for m in &self.movements {
+            apply(&mut stack, *m).context("failed to move")?;
+        }
+
+        Ok(stack.tops())
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const EXAMPLE: &'static str = include_str!("../example.input.txt");
+
+    type Input = Procedure;
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn parse_with(input: &str, mode: Mode) -> anyhow::Result<Self::Input> {
+        let lines = input.lines().collect::<Vec<&str>>();
+
+        let separator = lines
+            .iter()
+            .position(|line| line.is_empty())
+            .context("invalid input file, an empty line is required")?;
+
+        // The line right above the separator numbers the stacks.
+        let stacks_lines = &lines[..separator.saturating_sub(1)];
+        let stack = Stack::new(stacks_lines)?;
+
+        let movements = mode.collect(
+            lines.iter().copied().enumerate().skip(separator + 1),
+            |line| Movement::within(line, stack.width),
+        )?;
+
+        Ok(Procedure { stack, movements })
+    }
+
+    fn part_one(procedure: &Self::Input) -> anyhow::Result<String> {
+        procedure.rearrange(Stack::move_reversed)
+    }
+
+    fn part_two(procedure: &Self::Input) -> anyhow::Result<String> {
+        procedure.rearrange(Stack::r#move)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_stack() -> Stack {
+        Stack::new(&["    [D]    ", "[N] [C]    ", "[Z] [M] [P]"]).unwrap()
+    }
+
+    #[test]
+    fn parse_stack() {
+        let stack = example_stack();
+
+        assert_eq!(
+            stack.inner,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(stack.tops(), "NDP");
+    }
+
+    #[test]
+    fn parse_movement() {
+        let m = Movement::from_str("move 3 from 1 to 2").unwrap();
+
+        assert_eq!((m.nb, m.from, m.to), (3, 1, 2));
+    }
+
+    #[test]
+    fn invalid_stack() {
+        let err = Stack::new(&["    [D]    ", "[N] (C)    "]).unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 5));
+    }
+
+    #[test]
+    fn invalid_movement() {
+        assert_eq!(
+            Movement::from_str("move 1 from x to 2").unwrap_err().column,
+            13
+        );
+        assert_eq!(Movement::from_str("move 1 from").unwrap_err().column, 12);
+    }
+
+    #[test]
+    fn movement_keywords() {
+        let err = Movement::from_str("move 1 onto 2 to 3").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (8, "`from`"));
+
+        assert_eq!(
+            Movement::from_str("take 1 from 2 to 3").unwrap_err().column,
+            1
+        );
+        assert_eq!(
+            Movement::from_str("move 1 from 2 to 3 now")
+                .unwrap_err()
+                .column,
+            20
+        );
+    }
+
+    #[test]
+    fn unknown_stack() {
+        assert_eq!(
+            Movement::within("move 1 from 4 to 2", 3)
+                .unwrap_err()
+                .column,
+            13
+        );
+        assert_eq!(
+            Movement::within("move 1 from 1 to 0", 3)
+                .unwrap_err()
+                .column,
+            18
+        );
+        assert!(Day5::parse_with("\nmove 1 from 1 to 2", Mode::Strict).is_err());
+    }
+
+    #[test]
+    fn moves() {
+        let m = Movement::from_str("move 2 from 2 to 1").unwrap();
+
+        let mut stack = example_stack();
+        stack.r#move(m).unwrap();
+        assert_eq!(stack.inner[0], vec!['Z', 'N', 'C', 'D']);
+
+        let mut stack = example_stack();
+        stack.move_one_by_one(m).unwrap();
+        assert_eq!(stack.inner[0], vec!['Z', 'N', 'D', 'C']);
+
+        let mut stack = example_stack();
+        stack.move_reversed(m).unwrap();
+        assert_eq!(stack.inner[0], vec!['Z', 'N', 'D', 'C']);
+    }
+
+    #[test]
+    fn not_enough_crates() {
+        let m = Movement::from_str("move 4 from 2 to 1").unwrap();
+
+        assert!(example_stack().r#move(m).is_err());
+        assert!(example_stack().move_one_by_one(m).is_err());
+        assert!(example_stack().move_reversed(m).is_err());
+    }
+
+    #[test]
+    fn missing_stack() {
+        for m in [
+            "move 1 from 0 to 1",
+            "move 1 from 2 to 4",
+            "move 1 from 4 to 4",
+        ] {
+            let m = Movement::from_str(m).unwrap();
+
+            assert!(example_stack().r#move(m).is_err());
+            assert!(example_stack().move_one_by_one(m).is_err());
+            assert!(example_stack().move_reversed(m).is_err());
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Straightforward implementations, kept as reference for the optimized ones.
+
+use crate::{Movement, Procedure, Stack};
+
+/// Move the crates one by one on a spare stack, then one by one again on
+/// the destination, which keeps their order.
+pub fn move_keeping_order(stack: &mut Stack, m: Movement) -> anyhow::Result<()> {
+    let spare = stack.inner.len() + 1;
+    stack.inner.push(Vec::new());
+
+    let moved = stack
+        .move_one_by_one(Movement { to: spare, ..m })
+        .and_then(|()| stack.move_one_by_one(Movement { from: spare, ..m }));
+
+    stack.inner.pop();
+    moved
+}
+
+pub fn part_one(procedure: &Procedure) -> anyhow::Result<String> {
+    procedure.rearrange(Stack::move_one_by_one)
+}
+
+pub fn part_two(procedure: &Procedure) -> anyhow::Result<String> {
+    procedure.rearrange(move_keeping_order)
+}
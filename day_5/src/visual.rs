@@ -0,0 +1,103 @@
+use common::{
+    visual::{paint, Color, Visualize},
+    Part,
+};
+
+use crate::{Day5, Movement, Procedure, Stack};
+
+/// The stacks after a movement, `None` being the initial drawing.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub movement: Option<Movement>,
+    pub stacks: Vec<Vec<char>>,
+}
+
+impl Visualize for Day5 {
+    type Event = Step;
+
+    fn events(procedure: &Procedure, part: Part) -> anyhow::Result<Vec<Step>> {
+        let apply = match part {
+            Part::One => Stack::move_reversed,
+            Part::Two => Stack::r#move,
+        };
+
+        let mut stack = procedure.stack.clone();
+        let mut steps = vec![Step {
+            movement: None,
+            stacks: stack.inner.clone(),
+        }];
+
+        for m in &procedure.movements {
+            apply(&mut stack, *m)?;
+            steps.push(Step {
+                movement: Some(*m),
+                stacks: stack.inner.clone(),
+            });
+        }
+
+        Ok(steps)
+    }
+
+    fn render(_: &Procedure, step: &Step) -> String {
+        let mut out = match step.movement {
+            Some(m) => format!("move {} from {} to {}\n\n", m.nb, m.from, m.to),
+            None => "starting stacks\n\n".to_owned(),
+        };
+
+        // Crates that just landed are highlighted.
+        let moved = |stack: usize, level: usize| match step.movement {
+            Some(m) if m.from != m.to && stack + 1 == m.to => {
+                level + m.nb >= step.stacks[stack].len()
+            }
+            _ => false,
+        };
+
+        let height = step.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row = step
+                .stacks
+                .iter()
+                .enumerate()
+                .map(|(idx, stack)| match stack.get(level) {
+                    Some(c) if moved(idx, level) => paint(format!("[{c}]"), Color::Yellow),
+                    Some(c) => format!("[{c}]"),
+                    None => "   ".to_owned(),
+                })
+                .collect::<Vec<_>>();
+            out.push_str(&row.join(" "));
+            out.push('\n');
+        }
+
+        let numbers = (1..=step.stacks.len())
+            .map(|idx| format!(" {idx} "))
+            .collect::<Vec<_>>();
+        out.push_str(&numbers.join(" "));
+
+        let tops: String = step.stacks.iter().filter_map(|s| s.last()).collect();
+        out.push_str(&format!("\n\ntops: {}", paint(tops, Color::Green)));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+
+    #[test]
+    fn one_step_per_movement() {
+        let procedure = Day5::parse(Day5::EXAMPLE).unwrap();
+
+        for (part, tops) in [
+            (Part::One, Day5::part_one(&procedure).unwrap()),
+            (Part::Two, Day5::part_two(&procedure).unwrap()),
+        ] {
+            let steps = Day5::events(&procedure, part).unwrap();
+            assert_eq!(steps.len(), procedure.movements.len() + 1);
+
+            let last = Day5::render(&procedure, steps.last().unwrap());
+            assert!(last.ends_with(&paint(tops, Color::Green)));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+use day_5::Day5;
+
+common::example_tests!(Day5, "CMZ", "MCD");
@@ -0,0 +1,23 @@
+use common::{
+    generator::{Generator, Rng},
+    Solution,
+};
+use day_5::{naive, Day5};
+
+#[test]
+fn fast_and_naive_agree() {
+    let mut rng = Rng::new(0x5);
+
+    for size in 0..100 {
+        let procedure = Day5::parse(&Day5::generate(&mut rng, size)).unwrap();
+
+        assert_eq!(
+            Day5::part_one(&procedure).unwrap(),
+            naive::part_one(&procedure).unwrap()
+        );
+        assert_eq!(
+            Day5::part_two(&procedure).unwrap(),
+            naive::part_two(&procedure).unwrap()
+        );
+    }
+}
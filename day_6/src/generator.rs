@@ -0,0 +1,131 @@
+use anyhow::ensure;
+use common::generator::{Generator, Rng};
+
+use crate::{Day6, START_OF_MESSAGE_SIZE, START_OF_PACKET_SIZE};
+
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+impl Generator for Day6 {
+    /// A signal of `size` characters (at least 14) with markers at random
+    /// positions.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let len = size.max(START_OF_MESSAGE_SIZE);
+        let message = rng.range(START_OF_MESSAGE_SIZE..=len);
+        let packet = rng
+            .range(START_OF_PACKET_SIZE..=message - START_OF_MESSAGE_SIZE + START_OF_PACKET_SIZE);
+
+        with_markers(rng, len, packet, message).expect("marker positions are valid")
+    }
+}
+
+/// A signal of `len` characters whose start-of-packet and start-of-message
+/// markers are found after reading `packet` and `message` characters.
+///
+/// The run of distinct characters grows by at most one per character, so the
+/// message marker comes at least 10 characters after the packet marker.
+pub fn with_markers(
+    rng: &mut Rng,
+    len: usize,
+    packet: usize,
+    message: usize,
+) -> anyhow::Result<String> {
+    ensure!(
+        packet >= START_OF_PACKET_SIZE,
+        "the packet marker needs {START_OF_PACKET_SIZE} characters"
+    );
+    ensure!(
+        message >= packet + START_OF_MESSAGE_SIZE - START_OF_PACKET_SIZE,
+        "the message marker must come at least {} characters after the packet marker",
+        START_OF_MESSAGE_SIZE - START_OF_PACKET_SIZE
+    );
+    ensure!(
+        message <= len,
+        "the message marker must be within the signal"
+    );
+
+    let mut signal: Vec<u8> = Vec::with_capacity(len);
+    // Length of the run of distinct characters ending at the last one.
+    let mut distinct = 0;
+
+    for idx in 0..len {
+        let (min, max) = if idx + 1 < packet {
+            (
+                START_OF_PACKET_SIZE.saturating_sub(packet - 1 - idx),
+                START_OF_PACKET_SIZE - 1,
+            )
+        } else if idx + 1 == packet {
+            (START_OF_PACKET_SIZE, START_OF_PACKET_SIZE)
+        } else if idx + 1 < message {
+            (
+                START_OF_MESSAGE_SIZE.saturating_sub(message - 1 - idx),
+                START_OF_MESSAGE_SIZE - 1,
+            )
+        } else if idx + 1 == message {
+            (START_OF_MESSAGE_SIZE, START_OF_MESSAGE_SIZE)
+        } else {
+            (1, ALPHABET.len())
+        };
+        let target = rng.range(min.max(1)..=max.min(distinct + 1));
+
+        let next = if target == distinct + 1 {
+            // Extend the run with a character it does not contain yet.
+            let run = &signal[idx - distinct..];
+            let fresh = ALPHABET
+                .iter()
+                .filter(|c| !run.contains(c))
+                .copied()
+                .collect::<Vec<_>>();
+            *rng.choose(&fresh)
+        } else {
+            // Repeating the character right before the wanted run cuts it there.
+            signal[idx - target]
+        };
+
+        signal.push(next);
+        distinct = target;
+    }
+
+    Ok(String::from_utf8(signal).expect("the alphabet is ASCII") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+
+    #[test]
+    fn markers_where_requested() {
+        let mut rng = Rng::new(6);
+
+        for (len, packet, message) in [(14, 4, 14), (100, 4, 50), (1000, 300, 310), (500, 20, 500)]
+        {
+            let input = with_markers(&mut rng, len, packet, message).unwrap();
+            let signal = Day6::parse(&input).unwrap();
+
+            assert_eq!(signal.len(), len);
+            assert_eq!(Day6::part_one(&signal).unwrap(), packet);
+            assert_eq!(Day6::part_two(&signal).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn impossible_markers() {
+        let mut rng = Rng::new(6);
+
+        assert!(with_markers(&mut rng, 100, 3, 50).is_err());
+        assert!(with_markers(&mut rng, 100, 10, 19).is_err());
+        assert!(with_markers(&mut rng, 100, 10, 101).is_err());
+    }
+
+    #[test]
+    fn generated_input_is_valid() {
+        let mut rng = Rng::new(6);
+
+        for size in [0, 14, 100, 10_000] {
+            let signal = Day6::parse(&Day6::generate(&mut rng, size)).unwrap();
+
+            assert!(Day6::part_one(&signal).unwrap() <= Day6::part_two(&signal).unwrap());
+        }
+    }
+}
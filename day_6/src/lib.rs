@@ -0,0 +1,209 @@
+pub mod generator;
+pub mod naive;
+pub mod visual;
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io::BufRead,
+};
+
+use anyhow::{bail, Context};
+use common::{
+    input,
+    parse::{Mode, Scanner},
+    Solution, Streaming,
+};
+
+const START_OF_PACKET_SIZE: usize = 4;
+const START_OF_MESSAGE_SIZE: usize = 14;
+/// Shape of every character of the signal.
+const SIGNAL_CHAR: &str = "a lowercase letter";
+
+/// Number of characters read once the first `window_size` distinct
+/// characters have been received.
+pub fn find_marker(signal: &[char], window_size: usize) -> Option<usize> {
+    let mut detector = MarkerDetector::new(window_size);
+    detector
+        .found()
+        .or_else(|| signal.iter().find_map(|c| detector.push(*c)))
+}
+
+/// Look for a marker one character at a time, only remembering the last
+/// `window_size` ones.
+#[derive(Debug, Clone)]
+pub struct MarkerDetector {
+    window_size: usize,
+    window: VecDeque<char>,
+    /// Occurrences of each character inside the window.
+    counts: HashMap<char, usize>,
+    read: usize,
+}
+
+impl MarkerDetector {
+    pub fn new(window_size: usize) -> Self {
+        MarkerDetector {
+            window_size,
+            window: VecDeque::with_capacity(window_size + 1),
+            counts: HashMap::new(),
+            read: 0,
+        }
+    }
+
+    /// Number of characters read, if the last `window_size` ones are distinct.
+    pub fn found(&self) -> Option<usize> {
+        (self.counts.len() == self.window_size).then_some(self.read)
+    }
+
+    /// Receive `c`, returning the position of the marker if it ends here.
+    pub fn push(&mut self, c: char) -> Option<usize> {
+        self.read += 1;
+        self.window.push_back(c);
+        *self.counts.entry(c).or_default() += 1;
+
+        if self.window.len() > self.window_size {
+            let leaving = self.window.pop_front().expect("window is not empty");
+            match self.counts.get_mut(&leaving) {
+                Some(1) => {
+                    self.counts.remove(&leaving);
+                }
+                Some(count) => *count -= 1,
+                None => unreachable!("leaving char was counted"),
+            }
+        }
+
+        self.found()
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const EXAMPLE: &'static str = include_str!("../example.input.txt");
+
+    type Input = Vec<char>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    /// The signal is the first line, as [`Day6::stream`] reads it, its
+    /// malformed characters being skipped unless `mode` is strict.
+    fn parse_with(input: &str, mode: Mode) -> anyhow::Result<Self::Input> {
+        let line = input.split(['\n', '\r']).next().unwrap_or_default();
+
+        let signal = Scanner::whole(line, |s| {
+            s.many(|s| match s.char(SIGNAL_CHAR, |c| c.is_ascii_lowercase()) {
+                Ok(c) => Ok(Some(c)),
+                Err(err) if mode == Mode::Strict => Err(err),
+                Err(_) => {
+                    s.char(SIGNAL_CHAR, |_| true)?;
+                    Ok(None)
+                }
+            })
+        })?;
+        Ok(signal.into_iter().flatten().collect())
+    }
+
+    fn part_one(signal: &Self::Input) -> anyhow::Result<usize> {
+        find_marker(signal, START_OF_PACKET_SIZE).context("no start-of-packet marker found")
+    }
+
+    fn part_two(signal: &Self::Input) -> anyhow::Result<usize> {
+        find_marker(signal, START_OF_MESSAGE_SIZE).context("no start-of-message marker found")
+    }
+}
+
+impl Streaming for Day6 {
+    /// Read the signal, its first line, until both markers are found.
+    fn stream(reader: &mut dyn BufRead, mode: Mode) -> anyhow::Result<(usize, usize)> {
+        let mut packet = MarkerDetector::new(START_OF_PACKET_SIZE);
+        let mut message = MarkerDetector::new(START_OF_MESSAGE_SIZE);
+        let (mut packet_at, mut message_at) = (None, None);
+
+        for (x, c) in input::chars(reader).enumerate() {
+            let c = c?;
+            if c == '\n' || c == '\r' {
+                break;
+            }
+            if !c.is_ascii_lowercase() {
+                // The line is not kept around to show it in a `ParseError`.
+                if mode == Mode::Strict {
+                    bail!("expected {SIGNAL_CHAR} at line 1, column {}", x + 1);
+                }
+                continue;
+            }
+
+            packet_at = packet_at.or_else(|| packet.push(c));
+            message_at = message_at.or_else(|| message.push(c));
+            // Strict mode checks the rest of the signal all the same.
+            if packet_at.is_some() && message_at.is_some() && mode == Mode::Lenient {
+                break;
+            }
+        }
+
+        Ok((
+            packet_at.context("no start-of-packet marker found")?,
+            message_at.context("no start-of-message marker found")?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::ParseError;
+
+    fn marker(signal: &str, window_size: usize) -> Option<usize> {
+        find_marker(&signal.chars().collect::<Vec<_>>(), window_size)
+    }
+
+    #[test]
+    fn start_of_packet() {
+        assert_eq!(marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), Some(5));
+        assert_eq!(marker("nppdvjthqldpwncqszvftbrmjlhg", 4), Some(6));
+        assert_eq!(marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4), Some(11));
+    }
+
+    #[test]
+    fn only_the_first_line() {
+        for input in ["abc\ndef", "\nabcdefghijklmn", "abcdefghijklmn\r\nopq"] {
+            let signal = Day6::parse(input).unwrap();
+            let solved =
+                Day6::part_one(&signal).and_then(|one| Ok((one, Day6::part_two(&signal)?)));
+
+            assert_eq!(
+                Day6::stream(&mut input.as_bytes(), Mode::Lenient).ok(),
+                solved.ok(),
+                "{input:?}"
+            );
+        }
+        assert_eq!(Day6::parse("abcdefghijklmn\r\nopq").unwrap().len(), 14);
+    }
+
+    #[test]
+    fn strict_mode() {
+        let input = "abcd\u{e9}efghij1klmnop\n";
+
+        assert_eq!(Day6::parse(input).unwrap().len(), 16);
+        assert_eq!(
+            Day6::stream(&mut input.as_bytes(), Mode::Lenient).unwrap(),
+            (4, 14)
+        );
+
+        let err = Day6::parse_with(input, Mode::Strict).unwrap_err();
+        assert_eq!(err.downcast_ref::<ParseError>().unwrap().column, 5);
+        let err = Day6::stream(&mut input.as_bytes(), Mode::Strict).unwrap_err();
+        assert!(err.to_string().ends_with("column 5"));
+    }
+
+    #[test]
+    fn start_of_message() {
+        assert_eq!(marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 14), Some(23));
+        assert_eq!(marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14), Some(29));
+    }
+
+    #[test]
+    fn no_marker() {
+        assert_eq!(marker("aaaaaaa", 4), None);
+        assert_eq!(marker("abc", 4), None);
+    }
+}
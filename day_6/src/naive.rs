@@ -0,0 +1,15 @@
+//! Straightforward implementations, kept as reference for the optimized ones.
+
+use std::collections::HashSet;
+
+/// Same as [`crate::find_marker`], collecting every window in a set.
+pub fn find_marker(signal: &[char], window_size: usize) -> Option<usize> {
+    // No character at all makes for an empty window before reading any.
+    if window_size == 0 {
+        return Some(0);
+    }
+    signal
+        .windows(window_size)
+        .position(|w| w.iter().collect::<HashSet<_>>().len() == window_size)
+        .map(|position| position + window_size)
+}
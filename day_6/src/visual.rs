@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use common::{
+    visual::{paint, Color, Visualize},
+    Part,
+};
+
+use crate::{Day6, MarkerDetector, START_OF_MESSAGE_SIZE, START_OF_PACKET_SIZE};
+
+/// Characters shown on each side of the window.
+const CONTEXT: usize = 24;
+
+/// The window once `read` characters have been received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    pub read: usize,
+    pub size: usize,
+}
+
+impl Visualize for Day6 {
+    type Event = Window;
+
+    fn events(signal: &Vec<char>, part: Part) -> anyhow::Result<Vec<Window>> {
+        let size = match part {
+            Part::One => START_OF_PACKET_SIZE,
+            Part::Two => START_OF_MESSAGE_SIZE,
+        };
+
+        let mut detector = MarkerDetector::new(size);
+        let mut windows = Vec::new();
+        for (idx, c) in signal.iter().enumerate() {
+            let found = detector.push(*c);
+            windows.push(Window {
+                read: idx + 1,
+                size,
+            });
+            if found.is_some() {
+                break;
+            }
+        }
+
+        Ok(windows)
+    }
+
+    fn render(signal: &Vec<char>, window: &Window) -> String {
+        let start = window.read.saturating_sub(window.size);
+        let inside = &signal[start..window.read];
+
+        let mut counts = HashMap::new();
+        for c in inside {
+            *counts.entry(c).or_insert(0) += 1;
+        }
+        let found = inside.len() == window.size && counts.values().all(|count| *count == 1);
+
+        let before: String = signal[start.saturating_sub(CONTEXT)..start]
+            .iter()
+            .collect();
+        let after: String = signal[window.read..(window.read + CONTEXT).min(signal.len())]
+            .iter()
+            .collect();
+        let inside: String = inside
+            .iter()
+            .map(|c| match counts[c] {
+                1 => paint(c, Color::Green),
+                _ => paint(c, Color::Red),
+            })
+            .collect();
+
+        format!(
+            "{} characters read, looking for {} distinct ones\n\n{before}[{inside}]{after}\n\n{}",
+            window.read,
+            window.size,
+            if found {
+                paint(format!("marker found after {}", window.read), Color::Yellow)
+            } else {
+                "no marker yet".to_owned()
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+
+    #[test]
+    fn stops_on_the_marker() {
+        let signal = Day6::parse(Day6::EXAMPLE).unwrap();
+
+        let windows = Day6::events(&signal, Part::One).unwrap();
+        assert_eq!(windows.len(), Day6::part_one(&signal).unwrap());
+
+        let last = Day6::render(&signal, windows.last().unwrap());
+        assert!(last.contains("marker found after 7"));
+    }
+}
@@ -0,0 +1,3 @@
+use day_6::Day6;
+
+common::example_tests!(Day6, 7, 19);
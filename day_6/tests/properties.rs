@@ -0,0 +1,61 @@
+use common::{
+    generator::{Generator, Rng},
+    parse::Mode,
+    Solution, Streaming,
+};
+use day_6::{find_marker, naive, Day6};
+
+#[test]
+fn fast_and_naive_agree() {
+    let mut rng = Rng::new(0x6);
+
+    for size in 0..100 {
+        let signal = Day6::parse(&Day6::generate(&mut rng, size)).unwrap();
+
+        for window_size in 0..=16 {
+            assert_eq!(
+                find_marker(&signal, window_size),
+                naive::find_marker(&signal, window_size),
+                "window of {window_size}"
+            );
+        }
+    }
+}
+
+#[test]
+fn random_signals() {
+    let mut rng = Rng::new(0x60);
+
+    for _ in 0..200 {
+        let len = rng.range(0..=64);
+        let signal: Vec<char> = (0..len)
+            .map(|_| *rng.choose(&['a', 'b', 'c', 'd', 'e']))
+            .collect();
+
+        for window_size in 0..=6 {
+            assert_eq!(
+                find_marker(&signal, window_size),
+                naive::find_marker(&signal, window_size),
+                "{signal:?}, window of {window_size}"
+            );
+        }
+    }
+}
+
+#[test]
+fn streaming_agrees() {
+    let mut rng = Rng::new(0x66);
+
+    for size in 0..100 {
+        let input = Day6::generate(&mut rng, size);
+        let parsed = Day6::parse(&input).unwrap();
+
+        assert_eq!(
+            Day6::stream(&mut input.as_bytes(), Mode::Lenient).unwrap(),
+            (
+                Day6::part_one(&parsed).unwrap(),
+                Day6::part_two(&parsed).unwrap()
+            )
+        );
+    }
+}
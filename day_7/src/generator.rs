@@ -0,0 +1,144 @@
+use common::generator::{Generator, Rng};
+
+use crate::{Day7, REQUIRED_SIZE_FOR_UPDATE, TOTAL_SIZE};
+
+#[derive(Default)]
+struct Node {
+    files: Vec<(String, usize)>,
+    dirs: Vec<(String, Node)>,
+}
+
+impl Generator for Day7 {
+    /// Transcript exploring a tree of `size` directories besides the root.
+    /// The disk is mostly full enough for the update to need some space, but
+    /// sometimes already has room for it, and sometimes holds more than fits.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut root = Node::default();
+        let mut weights = Vec::new();
+        populate(rng, &mut root, size, &mut weights);
+
+        // Scale file sizes so the disk usage lands in the chosen range. Each
+        // file may round up to a unit more or down to a unit less.
+        let files = weights.len();
+        let least_used = TOTAL_SIZE - REQUIRED_SIZE_FOR_UPDATE + 1;
+        let used = match rng.range(0..=9) {
+            0 => rng.range(files..=TOTAL_SIZE - REQUIRED_SIZE_FOR_UPDATE - files),
+            1 => rng.range(TOTAL_SIZE + files + 1..=TOTAL_SIZE + REQUIRED_SIZE_FOR_UPDATE),
+            _ => rng.range(least_used..=TOTAL_SIZE.saturating_sub(files).max(least_used)),
+        };
+        let total_weight = weights.iter().sum::<usize>().max(1);
+        let mut sizes = weights
+            .iter()
+            .map(|w| (w * used / total_weight).max(1))
+            .collect::<Vec<_>>()
+            .into_iter();
+        assign_sizes(&mut root, &mut sizes);
+
+        let mut out = String::from("$ cd /\n");
+        transcript(&root, &mut out);
+        out
+    }
+}
+
+/// Add `count` directories under `dir`, and files everywhere, recording a
+/// random weight for each file.
+fn populate(rng: &mut Rng, dir: &mut Node, count: usize, weights: &mut Vec<usize>) {
+    for idx in 0..rng.range(1..=5) {
+        dir.files.push((format!("{}.{}", name(rng), idx), 0));
+        weights.push(rng.range(1..=1_000));
+    }
+
+    let mut remaining = count;
+    let nb_dirs = if count == 0 {
+        0
+    } else {
+        rng.range(1..=count.min(4))
+    };
+    for idx in 0..nb_dirs {
+        let below = if idx + 1 == nb_dirs {
+            remaining - 1
+        } else {
+            rng.range(0..=remaining - (nb_dirs - idx))
+        };
+        remaining -= below + 1;
+
+        let mut child = Node::default();
+        populate(rng, &mut child, below, weights);
+        dir.dirs.push((format!("{}{idx}", name(rng)), child));
+    }
+}
+
+fn assign_sizes(dir: &mut Node, sizes: &mut impl Iterator<Item = usize>) {
+    for (_, size) in &mut dir.files {
+        *size = sizes.next().expect("a size for every file");
+    }
+    for (_, child) in &mut dir.dirs {
+        assign_sizes(child, sizes);
+    }
+}
+
+fn transcript(dir: &Node, out: &mut String) {
+    out.push_str("$ ls\n");
+    for (name, _) in &dir.dirs {
+        out.push_str(&format!("dir {name}\n"));
+    }
+    for (name, size) in &dir.files {
+        out.push_str(&format!("{size} {name}\n"));
+    }
+
+    for (name, child) in &dir.dirs {
+        out.push_str(&format!("$ cd {name}\n"));
+        transcript(child, out);
+        out.push_str("$ cd ..\n");
+    }
+}
+
+fn name(rng: &mut Rng) -> String {
+    (0..rng.range(1..=8))
+        .map(|_| char::from(rng.range(b'a' as usize..=b'z' as usize) as u8))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+
+    fn count_dirs(dir: &crate::Directory) -> usize {
+        dir.content
+            .values()
+            .map(|entry| match entry {
+                crate::Entry::Dir(dir) => 1 + count_dirs(dir),
+                crate::Entry::File(_) => 0,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn generated_input_is_valid() {
+        let mut rng = Rng::new(7);
+
+        for size in [0, 1, 10, 200] {
+            let root = Day7::parse(&Day7::generate(&mut rng, size)).unwrap();
+
+            assert_eq!(count_dirs(&root), size);
+            assert_eq!(Day7::part_two(&root).is_ok(), root.size <= TOTAL_SIZE);
+        }
+    }
+
+    #[test]
+    fn disk_usage_varies() {
+        let mut rng = Rng::new(7);
+        let (mut roomy, mut full, mut oversize) = (false, false, false);
+
+        for _ in 0..100 {
+            let used = Day7::parse(&Day7::generate(&mut rng, 10)).unwrap().size;
+
+            roomy |= used <= TOTAL_SIZE - REQUIRED_SIZE_FOR_UPDATE;
+            full |= (TOTAL_SIZE - REQUIRED_SIZE_FOR_UPDATE + 1..=TOTAL_SIZE).contains(&used);
+            oversize |= used > TOTAL_SIZE;
+        }
+        assert!(roomy && full && oversize);
+    }
+}
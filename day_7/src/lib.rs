@@ -0,0 +1,338 @@
+pub mod generator;
+pub mod naive;
+pub mod visual;
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use common::{
+    parse::{Mode, ParseError, Scanner},
+    Solution,
+};
+
+#[derive(Debug, Clone)]
+pub enum Entry {
+    Dir(Directory),
+    File(usize),
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct Directory {
+    pub content: HashMap<String, Entry>,
+    pub size: usize,
+}
+
+impl Directory {
+    /// Root directory explored by the transcript `lines`. A `$ cd ..` at the
+    /// root is an error when strict, and does nothing otherwise.
+    pub fn new(lines: &[String], mode: Mode) -> Result<Self, ParseError> {
+        let mut iter = lines.iter().enumerate();
+
+        // The transcript starts at the root, the directory being built.
+        let first = iter.next().map_or("", |(_, line)| line.as_str());
+        Scanner::whole(first, |s| {
+            s.keyword("$")?;
+            s.keyword("cd")?;
+            s.keyword("/")
+        })
+        .map_err(|_| ParseError::new(first, 0, "`$ cd /`"))?;
+
+        let mut root_dir = Self::build_dir(&mut iter, 0, mode)?;
+        root_dir.compute_total_dirsize();
+        Ok(root_dir)
+    }
+
+    pub fn find_folder_to_remove(
+        &self,
+        current_folder_name: &str,
+        perfect_dir_size: usize,
+    ) -> (String, usize) {
+        self.content.iter().fold(
+            (current_folder_name.to_owned(), self.size),
+            |(best_fit_name, best_fit_size), (entry_name, entry)| match entry {
+                Entry::Dir(dir) => {
+                    let (name, size) =
+                        dir.find_folder_to_remove(entry_name.as_str(), perfect_dir_size);
+
+                    if size >= perfect_dir_size && size < best_fit_size {
+                        (name, size)
+                    } else {
+                        (best_fit_name, best_fit_size)
+                    }
+                }
+                Entry::File(_) => (best_fit_name, best_fit_size),
+            },
+        )
+    }
+
+    /// Sum of the sizes of every directory, this one included, whose size
+    /// is at most `limit`.
+    pub fn sum_dirs_at_most(&self, limit: usize) -> usize {
+        let own = if self.size <= limit { self.size } else { 0 };
+
+        own + self
+            .content
+            .values()
+            .map(|entry| match entry {
+                Entry::Dir(dir) => dir.sum_dirs_at_most(limit),
+                Entry::File(_) => 0,
+            })
+            .sum::<usize>()
+    }
+
+    /// Build a directory `depth` levels below the root from the transcript,
+    /// `lines` being numbered from 0. Malformed lines are skipped unless
+    /// `mode` is strict.
+    pub fn build_dir<'a>(
+        lines: &mut impl Iterator<Item = (usize, &'a String)>,
+        depth: usize,
+        mode: Mode,
+    ) -> Result<Directory, ParseError> {
+        let mut dir = Directory::default();
+        let mut in_cmd = false;
+
+        while let Some((idx, command)) = lines.next() {
+            let Some(line) = mode.line(idx, command, |line| Line::parse(line, in_cmd))? else {
+                continue;
+            };
+
+            match line {
+                Line::Ls => in_cmd = true,
+                Line::Cd { dir: "..", .. } if depth > 0 => break,
+                Line::Cd { dir: "..", offset } => {
+                    if mode == Mode::Strict {
+                        let expected = "a directory to enter, `/` having no parent";
+                        return Err(ParseError::new(command, offset, expected).on_line(idx + 1));
+                    }
+                }
+                Line::Cd { dir: focus, offset } => {
+                    if depth == MAX_DEPTH {
+                        let expected = format!("at most {MAX_DEPTH} nested directories");
+                        return Err(ParseError::new(command, offset, expected).on_line(idx + 1));
+                    }
+                    dir.content.insert(
+                        focus.to_owned(),
+                        Entry::Dir(Self::build_dir(lines, depth + 1, mode)?),
+                    );
+                }
+                Line::Dir(name) => {
+                    dir.content
+                        .insert(name.to_owned(), Entry::Dir(Default::default()));
+                }
+                Line::File { size, name } => {
+                    dir.content.insert(name.to_owned(), Entry::File(size));
+                }
+            };
+        }
+
+        Ok(dir)
+    }
+
+    /// Compute and cache the size of every directory, saturating instead of
+    /// overflowing on absurd file sizes.
+    pub fn compute_total_dirsize(&mut self) -> usize {
+        let out = self.content.iter_mut().fold(0usize, |acc, (_, v)| {
+            acc.saturating_add(match v {
+                Entry::Dir(ref mut inner) => inner.compute_total_dirsize(),
+                Entry::File(size) => *size,
+            })
+        });
+
+        self.size = out;
+        out
+    }
+}
+
+/// A line of the transcript.
+enum Line<'a> {
+    Ls,
+    /// `$ cd`, `offset` being where `dir` starts.
+    Cd {
+        dir: &'a str,
+        offset: usize,
+    },
+    Dir(&'a str),
+    File {
+        size: usize,
+        name: &'a str,
+    },
+}
+
+impl<'a> Line<'a> {
+    /// Line of the transcript, listed entries only being expected after
+    /// `$ ls` when `listing`.
+    fn parse(line: &'a str, listing: bool) -> Result<Self, ParseError> {
+        let expected = if listing {
+            "a `$ cd <dir>` or `$ ls` command, or a listed entry"
+        } else {
+            "a `$ cd <dir>` or `$ ls` command"
+        };
+
+        Scanner::whole(line, |s| {
+            let start = *s;
+            match s.word(expected)? {
+                "$" => match s.one_of(&[("ls", false), ("cd", true)], "`ls` or `cd`")? {
+                    false => Ok(Line::Ls),
+                    true => Ok(Line::Cd {
+                        offset: s.offset(),
+                        dir: s.word("a directory")?,
+                    }),
+                },
+                "dir" if listing => Ok(Line::Dir(s.word("a directory name")?)),
+                _ if listing => {
+                    *s = start;
+                    s.whitespace();
+                    Ok(Line::File {
+                        size: s.unsigned("a file size")?,
+                        name: s.word("a file name")?,
+                    })
+                }
+                _ => Err(start.error(expected)),
+            }
+        })
+    }
+}
+
+/// Directories a transcript may nest below the root. Directories are built,
+/// walked and dropped recursively, deeper ones would overflow the stack.
+pub const MAX_DEPTH: usize = 256;
+
+pub const SMALL_DIR_SIZE: usize = 100_000;
+pub const REQUIRED_SIZE_FOR_UPDATE: usize = 30_000_000;
+pub const TOTAL_SIZE: usize = 70_000_000;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    const EXAMPLE: &'static str = include_str!("../example.input.txt");
+
+    type Input = Directory;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse_with(input: &str, mode: Mode) -> anyhow::Result<Self::Input> {
+        let lines = input.lines().map(|s| s.to_owned()).collect::<Vec<String>>();
+
+        Ok(Directory::new(&lines, mode)?)
+    }
+
+    fn part_one(root: &Self::Input) -> anyhow::Result<usize> {
+        Ok(root.sum_dirs_at_most(SMALL_DIR_SIZE))
+    }
+
+    fn part_two(root: &Self::Input) -> anyhow::Result<usize> {
+        // Compute ideal size to remove
+        let unused_space = TOTAL_SIZE
+            .checked_sub(root.size)
+            .context("the filesystem is larger than the disk")?;
+        let perfect_dir_size = REQUIRED_SIZE_FOR_UPDATE.saturating_sub(unused_space);
+
+        // Find fodler to remove
+        let (_, size) = root.find_folder_to_remove("/", perfect_dir_size);
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Directory {
+        Day7::parse(Day7::EXAMPLE).unwrap()
+    }
+
+    #[test]
+    fn total_dirsize() {
+        let root = example();
+
+        assert_eq!(root.size, 48381165);
+        let Some(Entry::Dir(a)) = root.content.get("a") else {
+            panic!("missing directory a");
+        };
+        assert_eq!(a.size, 94853);
+    }
+
+    #[test]
+    fn invalid_transcript() {
+        let lines = ["$ cd /", "$ ls", "12a b.txt", "$ cd b"].map(str::to_owned);
+        let err = Directory::new(&lines, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+
+        let lines = ["$ cd /", "$ rm -rf"].map(str::to_owned);
+        let err = Directory::new(&lines, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let lines = ["$ cd /", "12 b.txt"].map(str::to_owned);
+        let err = Directory::new(&lines, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        for lines in [&["$ ls", "1 f"][..], &[]] {
+            let lines = lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>();
+            let err = Directory::new(&lines, Mode::Lenient).unwrap_err();
+            assert_eq!(
+                (err.line, err.column, err.expected.as_str()),
+                (1, 1, "`$ cd /`")
+            );
+        }
+    }
+
+    #[test]
+    fn lenient_transcript() {
+        let input = "$ cd /\n$ ls\n12a b.txt\n3 c.txt\n$ rm -rf\n$ cd d\n4 e\n$ ls\n5 f\n";
+
+        let root = Day7::parse(input).unwrap();
+        assert_eq!(root.size, 8);
+        assert!(!root.content.contains_key("b.txt"));
+
+        let err = Day7::parse_with(input, Mode::Strict).unwrap_err();
+        assert_eq!(err.downcast_ref::<ParseError>().unwrap().line, 3);
+    }
+
+    #[test]
+    fn leaving_the_root() {
+        let lines =
+            ["$ cd /", "$ cd ..", "$ ls", "5 f", "$ cd a", "$ ls", "7 g"].map(str::to_owned);
+
+        let root = Directory::new(&lines, Mode::Lenient).unwrap();
+        assert_eq!(root.size, 12);
+
+        let err = Directory::new(&lines, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+    }
+
+    #[test]
+    fn deeply_nested_directories() {
+        let transcript = |depth: usize| {
+            std::iter::once("$ cd /".to_owned())
+                .chain((0..depth).flat_map(|_| ["$ ls".to_owned(), "$ cd a".to_owned()]))
+                .chain(["$ ls".to_owned(), "1 f".to_owned()])
+                .collect::<Vec<_>>()
+        };
+
+        let root = Directory::new(&transcript(MAX_DEPTH), Mode::Strict).unwrap();
+        assert_eq!(root.size, 1);
+        assert_eq!(Day7::part_one(&root).unwrap(), MAX_DEPTH + 1);
+        assert_eq!(naive::part_one(&root).unwrap(), MAX_DEPTH + 1);
+        assert_eq!(Day7::part_two(&root).unwrap(), 1);
+
+        let err = Directory::new(&transcript(200_000), Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2 * MAX_DEPTH + 3, 6));
+    }
+
+    #[test]
+    fn sum_dirs_at_most() {
+        assert_eq!(example().sum_dirs_at_most(100_000), 95437);
+    }
+
+    #[test]
+    fn find_folder_to_remove() {
+        assert_eq!(
+            example().find_folder_to_remove("/", 8381165),
+            ("d".to_owned(), 24933642)
+        );
+    }
+}
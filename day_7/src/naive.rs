@@ -0,0 +1,49 @@
+//! Straightforward implementations, kept as reference for the optimized ones.
+
+use anyhow::Context;
+
+use crate::{Directory, Entry, REQUIRED_SIZE_FOR_UPDATE, SMALL_DIR_SIZE, TOTAL_SIZE};
+
+/// Size of `dir`, walking its whole content instead of trusting the cached
+/// [`Directory::size`].
+pub fn size(dir: &Directory) -> usize {
+    dir.content
+        .values()
+        .map(|entry| match entry {
+            Entry::Dir(inner) => size(inner),
+            Entry::File(size) => *size,
+        })
+        .sum()
+}
+
+/// Size of every directory below `dir`, `dir` included.
+pub fn dir_sizes(dir: &Directory) -> Vec<usize> {
+    let mut sizes = vec![size(dir)];
+
+    for entry in dir.content.values() {
+        if let Entry::Dir(inner) = entry {
+            sizes.extend(dir_sizes(inner));
+        }
+    }
+    sizes
+}
+
+pub fn part_one(root: &Directory) -> anyhow::Result<usize> {
+    Ok(dir_sizes(root)
+        .into_iter()
+        .filter(|size| *size <= SMALL_DIR_SIZE)
+        .sum())
+}
+
+pub fn part_two(root: &Directory) -> anyhow::Result<usize> {
+    let unused = TOTAL_SIZE
+        .checked_sub(size(root))
+        .context("the filesystem is larger than the disk")?;
+    let missing = REQUIRED_SIZE_FOR_UPDATE.saturating_sub(unused);
+
+    dir_sizes(root)
+        .into_iter()
+        .filter(|size| *size >= missing)
+        .min()
+        .ok_or_else(|| anyhow::anyhow!("no directory is big enough"))
+}
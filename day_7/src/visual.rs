@@ -0,0 +1,132 @@
+use anyhow::Context;
+use common::{
+    visual::{paint, Color, Visualize},
+    Part,
+};
+
+use crate::{Day7, Directory, Entry, REQUIRED_SIZE_FOR_UPDATE, SMALL_DIR_SIZE, TOTAL_SIZE};
+
+/// A directory entered during the traversal, along with the answer so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Visit {
+    pub path: Vec<String>,
+    pub part: Part,
+    pub answer: Option<usize>,
+}
+
+/// Subdirectories of `dir`, by name.
+fn subdirs(dir: &Directory) -> Vec<(&String, &Directory)> {
+    let mut subdirs = dir
+        .content
+        .iter()
+        .filter_map(|(name, entry)| match entry {
+            Entry::Dir(dir) => Some((name, dir)),
+            Entry::File(_) => None,
+        })
+        .collect::<Vec<_>>();
+    subdirs.sort_by_key(|(name, _)| *name);
+    subdirs
+}
+
+fn find<'a>(root: &'a Directory, path: &[String]) -> &'a Directory {
+    path.iter()
+        .fold(root, |dir, name| match &dir.content[name] {
+            Entry::Dir(dir) => dir,
+            Entry::File(_) => unreachable!("visits only go through directories"),
+        })
+}
+
+impl Visualize for Day7 {
+    type Event = Visit;
+
+    fn events(root: &Directory, part: Part) -> anyhow::Result<Vec<Visit>> {
+        let unused_space = TOTAL_SIZE
+            .checked_sub(root.size)
+            .context("the filesystem is larger than the disk")?;
+        let perfect_dir_size = REQUIRED_SIZE_FOR_UPDATE.saturating_sub(unused_space);
+
+        let mut visits = Vec::new();
+        let mut answer = None;
+        let mut stack = vec![(Vec::new(), root)];
+
+        while let Some((path, dir)) = stack.pop() {
+            answer = match part {
+                Part::One if dir.size <= SMALL_DIR_SIZE => Some(answer.unwrap_or(0) + dir.size),
+                Part::One => answer.or(Some(0)),
+                Part::Two if dir.size >= perfect_dir_size => {
+                    Some(answer.map_or(dir.size, |best: usize| best.min(dir.size)))
+                }
+                Part::Two => answer,
+            };
+
+            for (name, subdir) in subdirs(dir).into_iter().rev() {
+                let mut path = path.clone();
+                path.push(name.clone());
+                stack.push((path, subdir));
+            }
+
+            visits.push(Visit { path, part, answer });
+        }
+
+        Ok(visits)
+    }
+
+    fn render(root: &Directory, visit: &Visit) -> String {
+        let dir = find(root, &visit.path);
+        let counted = match visit.part {
+            Part::One => dir.size <= SMALL_DIR_SIZE,
+            Part::Two => visit.answer == Some(dir.size),
+        };
+
+        let mut out = format!("/{}\n\n", visit.path.join("/"));
+
+        let mut entries = dir.content.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(name, _)| *name);
+        for (name, entry) in entries {
+            match entry {
+                Entry::Dir(inner) => out.push_str(&format!(
+                    "  {} {}\n",
+                    paint(format!("{name}/"), Color::Cyan),
+                    inner.size
+                )),
+                Entry::File(size) => out.push_str(&format!("  {name} {size}\n")),
+            }
+        }
+
+        let size = format!("total size {}", dir.size);
+        out.push_str(&format!(
+            "\n{}\nanswer so far: {}",
+            if counted {
+                paint(size, Color::Green)
+            } else {
+                size
+            },
+            visit
+                .answer
+                .map_or_else(|| "none".to_owned(), |answer| answer.to_string())
+        ));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+
+    #[test]
+    fn ends_on_the_answer() {
+        let root = Day7::parse(Day7::EXAMPLE).unwrap();
+
+        for (part, answer) in [
+            (Part::One, Day7::part_one(&root).unwrap()),
+            (Part::Two, Day7::part_two(&root).unwrap()),
+        ] {
+            let visits = Day7::events(&root, part).unwrap();
+
+            assert_eq!(visits.len(), 4);
+            assert_eq!(visits.last().unwrap().answer, Some(answer));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+use day_7::Day7;
+
+common::example_tests!(Day7, 95437, 24933642);
@@ -0,0 +1,21 @@
+use common::{
+    generator::{Generator, Rng},
+    Solution,
+};
+use day_7::{naive, Day7};
+
+#[test]
+fn fast_and_naive_agree() {
+    let mut rng = Rng::new(0x7);
+
+    for size in 1..100 {
+        let root = Day7::parse(&Day7::generate(&mut rng, size)).unwrap();
+
+        assert_eq!(root.size, naive::size(&root));
+        assert_eq!(
+            Day7::part_one(&root).unwrap(),
+            naive::part_one(&root).unwrap()
+        );
+        assert_eq!(Day7::part_two(&root).ok(), naive::part_two(&root).ok());
+    }
+}
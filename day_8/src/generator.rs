@@ -0,0 +1,38 @@
+use common::generator::{Generator, Rng};
+
+use crate::Day8;
+
+impl Generator for Day8 {
+    /// A square forest of `size` by `size` trees.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+
+        (0..size)
+            .map(|_| {
+                let mut row = (0..size)
+                    .map(|_| char::from(b'0' + rng.range(0..=9) as u8))
+                    .collect::<String>();
+                row.push('\n');
+                row
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{parse::Mode, Solution};
+
+    use super::*;
+
+    #[test]
+    fn generated_input_is_valid() {
+        let mut rng = Rng::new(8);
+
+        for size in [1, 5, 50] {
+            let forest = Day8::parse_with(&Day8::generate(&mut rng, size), Mode::Strict).unwrap();
+
+            assert_eq!((forest.trees.width(), forest.trees.height()), (size, size));
+        }
+    }
+}
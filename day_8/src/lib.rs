@@ -0,0 +1,213 @@
+pub mod generator;
+pub mod naive;
+pub mod visual;
+
+use std::fmt::Display;
+
+use anyhow::Context;
+use common::{
+    grid::{Direction, Grid, Position},
+    parse::{Mode, ParseError},
+    Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tree(pub u8);
+
+#[derive(Debug, Clone)]
+pub struct Forest {
+    pub trees: Grid<Tree>,
+}
+
+impl Forest {
+    /// Forest of the tree heights of `buf`, malformed rows being skipped
+    /// unless `mode` is strict.
+    pub fn new(buf: &str, mode: Mode) -> Result<Self, ParseError> {
+        let trees = Grid::parse(buf, "a tree height digit", mode, |c| {
+            c.to_digit(10).map(|i| Tree(i as u8))
+        })?;
+
+        Ok(Forest { trees })
+    }
+
+    /// Every line of sight crossing the forest, as the coordinates of its
+    /// trees in order: rows both ways, then columns both ways.
+    fn lines_of_sight(&self) -> impl Iterator<Item = Vec<Position>> + '_ {
+        let (width, height) = (self.trees.width(), self.trees.height());
+        let rows = (0..height).flat_map(move |y| {
+            [
+                ((width - 1, y), Direction::Left),
+                ((0, y), Direction::Right),
+            ]
+        });
+        let columns = (0..width)
+            .flat_map(move |x| [((x, height - 1), Direction::Up), ((x, 0), Direction::Down)]);
+
+        rows.chain(columns).map(|(start, direction)| {
+            std::iter::once(start)
+                .chain(self.trees.ray(start, direction))
+                .collect()
+        })
+    }
+
+    /// Sweep every line of sight once, keeping the highest tree seen so far.
+    pub fn count_visible_trees(&self) -> usize {
+        let mut visible = self.trees.map(|_| false);
+
+        for line in self.lines_of_sight() {
+            let mut highest = None;
+            for position in line {
+                let tree = self.trees[position];
+                if highest < Some(tree) {
+                    visible[position] = true;
+                    highest = Some(tree);
+                }
+            }
+        }
+
+        visible.iter().filter(|(_, visible)| **visible).count()
+    }
+
+    pub fn is_visible(&self, x: usize, y: usize, height: u8) -> bool {
+        Direction::ORTHOGONAL.into_iter().any(|direction| {
+            self.trees
+                .ray((x, y), direction)
+                .all(|position| self.trees[position].0 < height)
+        })
+    }
+
+    /// Number of trees seen from `(x, y)` towards `direction` before the
+    /// view is blocked.
+    pub fn viewing_distance(&self, x: usize, y: usize, direction: Direction) -> usize {
+        let height = self.trees[(x, y)].0;
+        let mut distance = 0;
+
+        for position in self.trees.ray((x, y), direction) {
+            distance += 1;
+            if self.trees[position].0 >= height {
+                break;
+            }
+        }
+        distance
+    }
+
+    /// Product of the viewing distances in the four directions.
+    pub fn scenic_score(&self, x: usize, y: usize) -> usize {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .map(|direction| self.viewing_distance(x, y, direction))
+            .product()
+    }
+
+    /// Sweep every line of sight once, keeping a stack of the trees that may
+    /// still block the view of the next ones.
+    pub fn highest_scenic_score(&self) -> Option<usize> {
+        let mut scores = self.trees.map(|_| 1);
+
+        for line in self.lines_of_sight() {
+            let mut blocking: Vec<usize> = Vec::new();
+            for (idx, position) in line.iter().copied().enumerate() {
+                let height = self.trees[position];
+                while let Some(top) = blocking.last() {
+                    if self.trees[line[*top]] >= height {
+                        break;
+                    }
+                    blocking.pop();
+                }
+                scores[position] *= idx - blocking.last().unwrap_or(&0);
+                blocking.push(idx);
+            }
+        }
+
+        scores.iter().map(|(_, score)| *score).max()
+    }
+}
+
+impl Display for Tree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Display for Forest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.trees)
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    const EXAMPLE: &'static str = include_str!("../example.input.txt");
+
+    type Input = Forest;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse_with(input: &str, mode: Mode) -> anyhow::Result<Self::Input> {
+        Ok(Forest::new(input, mode)?)
+    }
+
+    fn part_one(forest: &Self::Input) -> anyhow::Result<usize> {
+        Ok(forest.count_visible_trees())
+    }
+
+    fn part_two(forest: &Self::Input) -> anyhow::Result<usize> {
+        forest.highest_scenic_score().context("empty forest")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Forest {
+        Day8::parse(Day8::EXAMPLE).unwrap()
+    }
+
+    #[test]
+    fn is_visible() {
+        let forest = example();
+
+        assert!(forest.is_visible(1, 1, 5));
+        assert!(!forest.is_visible(3, 1, 1));
+        assert!(!forest.is_visible(2, 2, 3));
+        assert!(forest.is_visible(0, 0, 3));
+    }
+
+    #[test]
+    fn invalid_forest() {
+        let err = Forest::new("303\n2x5\n", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        let err = Forest::new("303\n25\n", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn strict_mode() {
+        let input = "303\n2x5\n653\n";
+
+        assert_eq!(Day8::parse(input).unwrap().trees.height(), 2);
+        let err = Day8::parse_with(input, Mode::Strict).unwrap_err();
+        assert_eq!(err.downcast_ref::<ParseError>().unwrap().line, 2);
+    }
+
+    #[test]
+    fn empty_forest() {
+        let forest = Forest::new("", Mode::Strict).unwrap();
+
+        assert_eq!((forest.trees.width(), forest.trees.height()), (0, 0));
+        assert!(Day8::part_two(&forest).is_err());
+    }
+
+    #[test]
+    fn scenic_score() {
+        let forest = example();
+
+        assert_eq!(forest.scenic_score(2, 1), 4);
+        assert_eq!(forest.scenic_score(2, 3), 8);
+        assert_eq!(forest.scenic_score(0, 0), 0);
+    }
+}
@@ -0,0 +1,21 @@
+//! Straightforward implementations, kept as reference for the optimized ones.
+
+use crate::Forest;
+
+/// Look around every tree, one at a time.
+pub fn count_visible_trees(forest: &Forest) -> usize {
+    forest
+        .trees
+        .iter()
+        .filter(|((x, y), tree)| forest.is_visible(*x, *y, tree.0))
+        .count()
+}
+
+/// Compute the scenic score of every tree, one at a time.
+pub fn highest_scenic_score(forest: &Forest) -> Option<usize> {
+    forest
+        .trees
+        .positions()
+        .map(|(x, y)| forest.scenic_score(x, y))
+        .max()
+}
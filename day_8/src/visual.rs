@@ -0,0 +1,128 @@
+use common::{
+    grid::Direction,
+    visual::{paint, Color, Visualize},
+    Part,
+};
+
+use crate::{Day8, Forest};
+
+/// A tree looking towards `direction`, along with the answer so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Look {
+    pub x: usize,
+    pub y: usize,
+    pub direction: Direction,
+    /// Trees seen before the view is blocked.
+    pub distance: usize,
+    /// Whether a tree as high as this one blocks the view.
+    pub blocked: bool,
+    pub part: Part,
+    pub answer: usize,
+}
+
+impl Visualize for Day8 {
+    type Event = Look;
+
+    fn events(forest: &Forest, part: Part) -> anyhow::Result<Vec<Look>> {
+        let mut looks = Vec::new();
+        let mut answer = 0;
+
+        for ((x, y), height) in forest.trees.iter() {
+            let (mut visible, mut score) = (false, 1);
+
+            for direction in Direction::ORTHOGONAL {
+                let distance = forest.viewing_distance(x, y, direction);
+                let blocked = forest
+                    .trees
+                    .ray((x, y), direction)
+                    .take(distance)
+                    .any(|position| forest.trees[position] >= *height);
+
+                visible |= !blocked;
+                score *= distance;
+                looks.push(Look {
+                    x,
+                    y,
+                    direction,
+                    distance,
+                    blocked,
+                    part,
+                    answer,
+                });
+            }
+
+            // The tree counts once it has looked in every direction.
+            answer = match part {
+                Part::One => answer + usize::from(visible),
+                Part::Two => answer.max(score),
+            };
+            if let Some(look) = looks.last_mut() {
+                look.answer = answer;
+            }
+        }
+
+        Ok(looks)
+    }
+
+    fn render(forest: &Forest, look: &Look) -> String {
+        let seen = forest
+            .trees
+            .ray((look.x, look.y), look.direction)
+            .take(look.distance)
+            .collect::<Vec<_>>();
+        let blocker = seen.last().filter(|_| look.blocked);
+
+        let mut out = String::new();
+        for (y, row) in forest.trees.rows().enumerate() {
+            for (x, tree) in row.iter().enumerate() {
+                let height = tree.0.to_string();
+                out.push_str(&if (x, y) == (look.x, look.y) {
+                    paint(height, Color::Reverse)
+                } else if blocker == Some(&(x, y)) {
+                    paint(height, Color::Red)
+                } else if seen.contains(&(x, y)) {
+                    paint(height, Color::Yellow)
+                } else {
+                    height
+                });
+            }
+            out.push('\n');
+        }
+
+        let sight = match (look.part, look.blocked) {
+            (Part::One, true) => "hidden".to_owned(),
+            (Part::One, false) => paint("visible", Color::Green),
+            (Part::Two, _) => format!("viewing distance {}", look.distance),
+        };
+        out.push_str(&format!(
+            "\ntree ({}, {}) looking {:?}: {sight}\nanswer so far: {}",
+            look.x, look.y, look.direction, look.answer
+        ));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+
+    #[test]
+    fn ends_on_the_answer() {
+        let forest = Day8::parse(Day8::EXAMPLE).unwrap();
+
+        for (part, answer) in [
+            (Part::One, Day8::part_one(&forest).unwrap()),
+            (Part::Two, Day8::part_two(&forest).unwrap()),
+        ] {
+            let looks = Day8::events(&forest, part).unwrap();
+
+            assert_eq!(
+                looks.len(),
+                4 * forest.trees.width() * forest.trees.height()
+            );
+            assert_eq!(looks.last().unwrap().answer, answer);
+        }
+    }
+}
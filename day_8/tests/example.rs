@@ -0,0 +1,3 @@
+use day_8::Day8;
+
+common::example_tests!(Day8, 21, 8);
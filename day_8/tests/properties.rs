@@ -0,0 +1,25 @@
+use common::{
+    generator::{Generator, Rng},
+    Solution,
+};
+use day_8::{naive, Day8};
+
+#[test]
+fn fast_and_naive_agree() {
+    let mut rng = Rng::new(0x8);
+
+    for size in 1..60 {
+        let forest = Day8::parse(&Day8::generate(&mut rng, size)).unwrap();
+
+        assert_eq!(
+            forest.count_visible_trees(),
+            naive::count_visible_trees(&forest),
+            "\n{forest}"
+        );
+        assert_eq!(
+            forest.highest_scenic_score(),
+            naive::highest_scenic_score(&forest),
+            "\n{forest}"
+        );
+    }
+}
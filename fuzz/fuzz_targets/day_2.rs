@@ -0,0 +1,11 @@
+#![no_main]
+
+use common::{parse::Mode, Solution};
+use day_2::Day2;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for mode in [Mode::Lenient, Mode::Strict] {
+        let _ = Day2::parse_with(input, mode);
+    }
+});
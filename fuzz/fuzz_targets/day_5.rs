@@ -0,0 +1,11 @@
+#![no_main]
+
+use common::{parse::Mode, Solution};
+use day_5::Day5;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for mode in [Mode::Lenient, Mode::Strict] {
+        let _ = Day5::parse_with(input, mode);
+    }
+});